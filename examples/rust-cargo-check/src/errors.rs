@@ -0,0 +1,5 @@
+/// Intentional type error reported by cargo check (E0308)
+pub fn answer() -> i32 {
+    let answer: i32 = "forty-two";
+    answer
+}
@@ -0,0 +1,5 @@
+//! Crate with a compile error in a declared module, for cargo check diagnostics
+
+mod errors;
+
+pub use errors::answer;
@@ -0,0 +1,7 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Area of a rectangle
+pub fn rectangle_area(width: f64, height: f64) -> f64 {
+    width * height
+}
@@ -0,0 +1,3 @@
+//! Crate with a module file and unused imports, for move file and organize imports
+
+pub mod geometry;
@@ -0,0 +1,26 @@
+import { describe, it, expect } from "vitest";
+import { selectByIndexOrLabel } from "./selection.ts";
+
+describe("selectByIndexOrLabel", () => {
+  const runnables = ["test-mod tests", "test tests::test_greet", "test tests::test_calculator"];
+  const noun = { singular: "runnable", plural: "runnables", labelName: "label" };
+  const select = (index?: number, label?: string) =>
+    selectByIndexOrLabel(runnables, (runnable) => runnable, noun, index, label);
+
+  it("should select by 1-based index", () => {
+    expect(select(2)).toBe("test tests::test_greet");
+  });
+
+  it("should prefer an exact label over substring matches", () => {
+    expect(select(undefined, "test-mod tests")).toBe("test-mod tests");
+    expect(select(undefined, "CALCULATOR")).toBe("test tests::test_calculator");
+  });
+
+  it("should list the available entries when the selection fails", () => {
+    expect(() => select(4)).toThrow(
+      "No runnable with index 4. Available runnables:\n  1. test-mod tests"
+    );
+    expect(() => select(undefined, "tests::")).toThrow('Multiple runnables match "tests::"');
+    expect(() => select()).toThrow("Either index or label must be specified");
+  });
+});
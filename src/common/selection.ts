@@ -0,0 +1,47 @@
+/**
+ * Pick one entry of a numbered list by its 1-based index or by its label
+ * (exact match, or a unique case-insensitive substring)
+ * @param items Entries in listing order
+ * @param getLabel Label of an entry as listed to the user
+ * @param noun Singular and plural name of the entries, used in errors
+ * @throws When no entry or several entries match, listing the available ones
+ */
+export function selectByIndexOrLabel<T>(
+  items: T[],
+  getLabel: (item: T) => string,
+  noun: { singular: string; plural: string; labelName: string },
+  index?: number,
+  label?: string
+): T {
+  const available = items
+    .map((item, i) => `  ${i + 1}. ${getLabel(item)}`)
+    .join("\n");
+
+  if (index !== undefined) {
+    const item = items[index - 1];
+    if (item === undefined) {
+      throw new Error(
+        `No ${noun.singular} with index ${index}. Available ${noun.plural}:\n${available}`
+      );
+    }
+    return item;
+  }
+
+  if (label !== undefined) {
+    const exact = items.find((item) => getLabel(item) === label);
+    if (exact !== undefined) {
+      return exact;
+    }
+    const matches = items.filter((item) =>
+      getLabel(item).toLowerCase().includes(label.toLowerCase())
+    );
+    if (matches.length === 1) {
+      return matches[0];
+    }
+    throw new Error(
+      `${matches.length === 0 ? "No" : "Multiple"} ${noun.plural} match "${label}". Available ${noun.plural}:\n${available}`
+    );
+  }
+
+  throw new Error(`Either index or ${noun.labelName} must be specified`);
+}
@@ -0,0 +1,11 @@
+/**
+ * Test results shared by the Rust (libtest) and vitest/jest runners
+ */
+
+export interface TestCaseResult {
+  name: string;
+  status: "passed" | "failed" | "ignored";
+  message?: string;
+  // Absolute path and 1-based line of the failure (or the test)
+  location?: { filePath: string; line: number };
+}
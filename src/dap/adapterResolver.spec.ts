@@ -0,0 +1,55 @@
+import { describe, it, expect, beforeEach, afterEach } from "vitest";
+import * as fs from "fs";
+import * as os from "os";
+import * as path from "path";
+import {
+  isAdapterAvailable,
+  launchesBeforeConfigurationDone,
+  resolveAdapter,
+} from "./adapterResolver.ts";
+
+describe("lldb adapter", () => {
+  let binDir: string;
+  let savedPath: string | undefined;
+  let savedLldbDapPath: string | undefined;
+
+  beforeEach(() => {
+    binDir = fs.mkdtempSync(path.join(os.tmpdir(), "lsmcp-lldb-"));
+    savedPath = process.env.PATH;
+    savedLldbDapPath = process.env.LLDB_DAP_PATH;
+    process.env.PATH = binDir;
+    delete process.env.LLDB_DAP_PATH;
+  });
+
+  afterEach(() => {
+    process.env.PATH = savedPath;
+    if (savedLldbDapPath === undefined) {
+      delete process.env.LLDB_DAP_PATH;
+    } else {
+      process.env.LLDB_DAP_PATH = savedLldbDapPath;
+    }
+    fs.rmSync(binDir, { recursive: true, force: true });
+  });
+
+  it("should not be available without lldb-dap", () => {
+    expect(isAdapterAvailable("lldb")).toBe(false);
+  });
+
+  it("should prefer lldb-dap over lldb-vscode and pick the newest version", () => {
+    for (const name of ["lldb-vscode-14", "lldb-dap-17", "lldb-dap-18"]) {
+      fs.writeFileSync(path.join(binDir, name), "");
+    }
+    expect(resolveAdapter("lldb").command).toBe(path.join(binDir, "lldb-dap-18"));
+    expect(isAdapterAvailable("lldb")).toBe(true);
+  });
+
+  it("should honor LLDB_DAP_PATH", () => {
+    process.env.LLDB_DAP_PATH = "/opt/llvm/bin/lldb-dap";
+    expect(resolveAdapter("lldb")).toEqual({
+      command: "/opt/llvm/bin/lldb-dap",
+      args: [],
+    });
+    expect(launchesBeforeConfigurationDone("lldb")).toBe(true);
+    expect(launchesBeforeConfigurationDone("node")).toBe(false);
+  });
+});
@@ -14,6 +14,48 @@ interface AdapterInfo {
   command: string;
   args: string[];
   capabilities?: Record<string, any>;
+  // Locate the adapter executable at resolution time
+  findCommand?: () => string | undefined;
+  // The adapter sends `initialized` only after the launch request
+  launchBeforeConfigurationDone?: boolean;
+}
+
+// lldb-dap was called lldb-vscode before LLVM 18; distributions add version suffixes
+const LLDB_DAP_PATTERN = /^lldb-(dap|vscode)(-\d+)?(\.exe)?$/;
+
+/**
+ * Find an lldb DAP adapter: $LLDB_DAP_PATH, then lldb-dap / lldb-vscode in PATH.
+ * CodeLLDB (`codelldb`) is not supported: it serves DAP over TCP rather than
+ * stdio and takes differently shaped launch arguments (e.g. `env` as an object).
+ */
+function findLldbDap(): string | undefined {
+  if (process.env.LLDB_DAP_PATH) {
+    return process.env.LLDB_DAP_PATH;
+  }
+
+  for (const dir of (process.env.PATH || "").split(path.delimiter)) {
+    if (!dir) continue;
+    let entries: string[];
+    try {
+      entries = fs.readdirSync(dir);
+    } catch {
+      continue;
+    }
+    // Prefer the unversioned name, then the newest versioned one
+    const matches = entries
+      .filter((entry) => LLDB_DAP_PATTERN.test(entry))
+      .sort((a, b) => lldbDapRank(b) - lldbDapRank(a));
+    if (matches.length > 0) {
+      return path.join(dir, matches[0]);
+    }
+  }
+  return undefined;
+}
+
+function lldbDapRank(name: string): number {
+  const match = name.match(LLDB_DAP_PATTERN)!;
+  const version = match[2] ? parseInt(match[2].slice(1), 10) : 1000;
+  return (match[1] === "dap" ? 10000 : 0) + version;
 }
 
 /**
@@ -29,6 +71,12 @@ const BUILTIN_ADAPTERS: Record<string, AdapterInfo> = {
       supportsEvaluateForHovers: true,
     }
   },
+  lldb: {
+    command: "lldb-dap",
+    args: [],
+    findCommand: findLldbDap,
+    launchBeforeConfigurationDone: true,
+  },
   // Add more built-in adapters here in the future
   // python: { ... },
   // go: { ... },
@@ -41,7 +89,8 @@ export function resolveAdapter(adapter: string): { command: string; args: string
   // Check if it's a built-in adapter
   if (BUILTIN_ADAPTERS[adapter]) {
     const info = BUILTIN_ADAPTERS[adapter];
-    return { command: info.command, args: [...info.args] };
+    const command = info.findCommand?.() ?? info.command;
+    return { command, args: [...info.args] };
   }
   
   // Check if it's a path to an adapter
@@ -80,13 +129,21 @@ export function getAdapterCapabilities(adapter: string): Record<string, any> {
   return {};
 }
 
+/**
+ * Whether the adapter expects the launch request before configurationDone
+ */
+export function launchesBeforeConfigurationDone(adapter: string): boolean {
+  return BUILTIN_ADAPTERS[adapter]?.launchBeforeConfigurationDone ?? false;
+}
+
 /**
  * Check if an adapter is available
  */
 export function isAdapterAvailable(adapter: string): boolean {
-  // Built-in adapters are always available
+  // Built-in adapters are available unless their executable has to be found
   if (BUILTIN_ADAPTERS[adapter]) {
-    return true;
+    const { findCommand } = BUILTIN_ADAPTERS[adapter];
+    return !findCommand || findCommand() !== undefined;
   }
   
   // Check if it's a file path
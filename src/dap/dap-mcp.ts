@@ -6,6 +6,13 @@
 import { z } from "zod";
 import { BaseMcpServer, ToolDef } from "../mcp/_mcplib.ts";
 import { createDebugSession, DebugSession } from "./index.ts";
+import {
+  buildRustProgram,
+  buildRustTest,
+  getRustLldbInitCommands,
+  isCargoProject,
+  toLldbEnv,
+} from "./rustSupport.ts";
 import type { StoppedEvent, Variable, StackFrame, Scope } from "./types.ts";
 import * as fs from "fs/promises";
 import * as path from "path";
@@ -125,136 +132,274 @@ function formatVariable(variable: Variable, indent: string): string {
   return result;
 }
 
+// Record breakpoints for a source, replacing the existing ones
+function recordBreakpoints(
+  sessionInfo: SessionInfo,
+  source: string,
+  lines: number[],
+  conditions?: (string | undefined)[]
+): BreakpointInfo[] {
+  const breakpoints: BreakpointInfo[] = lines.map((line: number, index: number) => ({
+    id: ++breakpointIdCounter,
+    source,
+    line,
+    condition: conditions?.[index],
+    hitCount: 0,
+    verified: false,
+    createdAt: new Date(),
+  }));
+
+  sessionInfo.breakpoints.set(source, breakpoints);
+  return breakpoints;
+}
+
+interface LaunchSessionOptions {
+  sessionId: string;
+  adapter: string;
+  adapterArgs?: string[];
+  program: string;
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string;
+  stopOnEntry?: boolean;
+  enableLogging?: boolean;
+  // Breakpoints set before the program starts running
+  breakpoints?: Array<{ source: string; line: number; condition?: string }>;
+}
+
+// Create a session, launch the program and track its state
+async function launchSession(options: LaunchSessionOptions): Promise<string[]> {
+  if (sessions.has(options.sessionId)) {
+    const existing = sessions.get(options.sessionId)!;
+    if (existing.state !== SessionState.TERMINATED && existing.state !== SessionState.ERROR) {
+      throw new Error(`Session ${options.sessionId} already exists and is ${existing.state}`);
+    }
+    // Clean up terminated/error session
+    sessions.delete(options.sessionId);
+  }
+
+  const adapterLaunchArgs: Record<string, any> = {};
+  if (options.adapter === "lldb") {
+    adapterLaunchArgs.initCommands = getRustLldbInitCommands(options.cwd);
+  }
+
+  const session = createDebugSession({
+    adapter: options.adapter,
+    adapterArgs: options.adapterArgs,
+    clientID: `mcp-dap-${options.sessionId}`,
+    clientName: "MCP DAP Server",
+  });
+
+  // Create log file if logging is enabled
+  let logFile: string | undefined;
+  if (options.enableLogging) {
+    await ensureLogsDirectory();
+    const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
+    logFile = path.join(DEBUG_LOGS_DIR, `debug-${options.sessionId}-${timestamp}.jsonl`);
+  }
+
+  const sessionInfo: SessionInfo = {
+    session,
+    state: SessionState.CONNECTING,
+    createdAt: new Date(),
+    lastActivityAt: new Date(),
+    program: options.program,
+    adapter: options.adapter,
+    breakpoints: new Map(),
+    events: [],
+    logFile,
+  };
+
+  sessions.set(options.sessionId, sessionInfo);
+
+  // Set up event handlers
+  session.on("stopped", async (event: StoppedEvent) => {
+    sessionInfo.state = SessionState.STOPPED;
+    sessionInfo.lastActivityAt = new Date();
+    await logDebugEvent(options.sessionId, "stopped", event);
+
+    // Update breakpoint hit counts
+    if (event.reason === "breakpoint" && event.hitBreakpointIds) {
+      for (const bpId of event.hitBreakpointIds) {
+        for (const [_, bps] of sessionInfo.breakpoints) {
+          const bp = bps.find(b => b.id === bpId);
+          if (bp) {
+            bp.hitCount++;
+            await logDebugEvent(options.sessionId, "breakpoint_hit", {
+              breakpointId: bp.id,
+              source: bp.source,
+              line: bp.line,
+              hitCount: bp.hitCount
+            });
+          }
+        }
+      }
+    }
+  });
+
+  session.on("continued", async () => {
+    sessionInfo.state = SessionState.RUNNING;
+    sessionInfo.lastActivityAt = new Date();
+    await logDebugEvent(options.sessionId, "continued", {});
+  });
+
+  session.on("terminated", async () => {
+    sessionInfo.state = SessionState.TERMINATED;
+    sessionInfo.lastActivityAt = new Date();
+    await logDebugEvent(options.sessionId, "terminated", {});
+  });
+
+  session.on("output", async (event) => {
+    sessionInfo.lastActivityAt = new Date();
+    await logDebugEvent(options.sessionId, "output", event);
+  });
+
+  // Group initial breakpoints by source file
+  const initialBreakpoints = new Map<string, { lines: number[]; conditions: (string | undefined)[] }>();
+  for (const bp of options.breakpoints ?? []) {
+    const entry = initialBreakpoints.get(bp.source) ?? { lines: [], conditions: [] };
+    entry.lines.push(bp.line);
+    entry.conditions.push(bp.condition);
+    initialBreakpoints.set(bp.source, entry);
+  }
+
+  try {
+    await session.connect();
+    sessionInfo.state = SessionState.CONNECTED;
+    await logDebugEvent(options.sessionId, "connected", { adapter: options.adapter });
+
+    await session.launch(
+      options.program,
+      {
+        args: options.args,
+        env: options.adapter === "lldb" ? toLldbEnv(options.env) : options.env,
+        cwd: options.cwd,
+        stopOnEntry: options.stopOnEntry,
+        noDebug: false,
+        ...adapterLaunchArgs,
+      },
+      Array.from(initialBreakpoints, ([source, { lines, conditions }]) => ({
+        source,
+        lines,
+        conditions,
+      }))
+    );
+
+    for (const [source, { lines, conditions }] of initialBreakpoints) {
+      const breakpoints = recordBreakpoints(sessionInfo, source, lines, conditions);
+      await logDebugEvent(options.sessionId, "breakpoints_set", {
+        source,
+        breakpoints: breakpoints.map(bp => ({
+          id: bp.id,
+          line: bp.line,
+          condition: bp.condition
+        }))
+      });
+    }
+
+    // The program may already have stopped at an initial breakpoint
+    if (!options.stopOnEntry && sessionInfo.state === SessionState.CONNECTED) {
+      sessionInfo.state = SessionState.RUNNING;
+    }
+
+    await logDebugEvent(options.sessionId, "launched", { 
+      program: options.program,
+      args: options.args,
+      cwd: options.cwd
+    });
+
+    const result = [`Debug session ${options.sessionId} launched for ${options.program} (state: ${sessionInfo.state})`];
+    if (logFile) {
+      result.push(`Logging to: ${logFile}`);
+    }
+    return result;
+  } catch (error) {
+    sessionInfo.state = SessionState.ERROR;
+    await logDebugEvent(options.sessionId, "error", { error: String(error) });
+    sessions.delete(options.sessionId);
+    throw error;
+  }
+}
+
 // Tool definitions
 const launchDebugSessionTool: ToolDef<z.ZodType> = {
   name: "debug_launch",
   description: "Launch a new debug session for a program",
   schema: z.object({
     sessionId: z.string().describe("Unique identifier for this debug session"),
-    adapter: z.string().describe("Debug adapter to use (e.g., 'node', 'python')"),
+    adapter: z.string().describe("Debug adapter to use (e.g., 'node', 'lldb', 'python')"),
     adapterArgs: z.array(z.string()).optional().describe("Arguments for the debug adapter"),
-    program: z.string().describe("Path to the program to debug"),
+    program: z.string().describe("Path to the program to debug (for 'lldb', a Cargo project directory or Cargo.toml is built first)"),
     args: z.array(z.string()).optional().describe("Program arguments"),
     env: z.record(z.string()).optional().describe("Environment variables"),
     cwd: z.string().optional().describe("Working directory"),
     stopOnEntry: z.boolean().optional().describe("Stop at program entry point"),
     enableLogging: z.boolean().optional().describe("Enable debug event logging to file"),
+    cargoBin: z.string().optional().describe("Binary target to build when program is a Cargo project"),
+    release: z.boolean().optional().describe("Build the Cargo project with --release"),
   }),
   execute: async (args) => {
-    if (sessions.has(args.sessionId)) {
-      const existing = sessions.get(args.sessionId)!;
-      if (existing.state !== SessionState.TERMINATED && existing.state !== SessionState.ERROR) {
-        throw new Error(`Session ${args.sessionId} already exists and is ${existing.state}`);
-      }
-      // Clean up terminated/error session
-      sessions.delete(args.sessionId);
+    // Build Cargo projects so lldb gets a fresh binary with debug info
+    let program = args.program;
+    let cwd = args.cwd;
+    const built = args.adapter === "lldb" && isCargoProject(args.program);
+    if (built) {
+      const result = await buildRustProgram(args.program, {
+        bin: args.cargoBin,
+        release: args.release,
+      });
+      program = result.program;
+      cwd = cwd ?? result.cwd;
     }
 
-    const session = createDebugSession({
-      adapter: args.adapter,
-      adapterArgs: args.adapterArgs,
-      clientID: `mcp-dap-${args.sessionId}`,
-      clientName: "MCP DAP Server",
-    });
-
-    // Create log file if logging is enabled
-    let logFile: string | undefined;
-    if (args.enableLogging) {
-      await ensureLogsDirectory();
-      const timestamp = new Date().toISOString().replace(/[:.]/g, "-");
-      logFile = path.join(DEBUG_LOGS_DIR, `debug-${args.sessionId}-${timestamp}.jsonl`);
+    const result = await launchSession({ ...args, program, cwd });
+    if (built) {
+      result.splice(1, 0, `Built with cargo build from ${args.program}`);
     }
+    return result.join("\n");
+  },
+};
 
-    const sessionInfo: SessionInfo = {
-      session,
-      state: SessionState.CONNECTING,
-      createdAt: new Date(),
-      lastActivityAt: new Date(),
-      program: args.program,
-      adapter: args.adapter,
-      breakpoints: new Map(),
-      events: [],
-      logFile,
-    };
-
-    sessions.set(args.sessionId, sessionInfo);
-
-    // Set up event handlers
-    session.on("stopped", async (event: StoppedEvent) => {
-      sessionInfo.state = SessionState.STOPPED;
-      sessionInfo.lastActivityAt = new Date();
-      await logDebugEvent(args.sessionId, "stopped", event);
-
-      // Update breakpoint hit counts
-      if (event.reason === "breakpoint" && event.hitBreakpointIds) {
-        for (const bpId of event.hitBreakpointIds) {
-          for (const [_, bps] of sessionInfo.breakpoints) {
-            const bp = bps.find(b => b.id === bpId);
-            if (bp) {
-              bp.hitCount++;
-              await logDebugEvent(args.sessionId, "breakpoint_hit", {
-                breakpointId: bp.id,
-                source: bp.source,
-                line: bp.line,
-                hitCount: bp.hitCount
-              });
-            }
-          }
-        }
-      }
-    });
-
-    session.on("continued", async () => {
-      sessionInfo.state = SessionState.RUNNING;
-      sessionInfo.lastActivityAt = new Date();
-      await logDebugEvent(args.sessionId, "continued", {});
-    });
+const launchTestTool: ToolDef<z.ZodType> = {
+  name: "debug_launch_test",
+  description: "Build the tests of a Cargo project and debug a single test under the lldb adapter",
+  schema: z.object({
+    sessionId: z.string().describe("Unique identifier for this debug session"),
+    program: z.string().describe("Cargo project directory or Cargo.toml"),
+    testName: z.string().describe("Test to run, as a full path (tests::test_calculator) or function name"),
+    package: z.string().optional().describe("Package to build tests for in a workspace"),
+    breakpoints: z.array(z.object({
+      source: z.string().describe("Source file path"),
+      line: z.number().describe("Line number"),
+      condition: z.string().optional().describe("Conditional expression"),
+    })).optional().describe("Breakpoints to set before the test starts running"),
+    env: z.record(z.string()).optional().describe("Environment variables"),
+    stopOnEntry: z.boolean().optional().describe("Stop at program entry point"),
+    enableLogging: z.boolean().optional().describe("Enable debug event logging to file"),
+  }),
+  execute: async (args) => {
+    if (!isCargoProject(args.program)) {
+      throw new Error(`${args.program} is not a Cargo project`);
+    }
 
-    session.on("terminated", async () => {
-      sessionInfo.state = SessionState.TERMINATED;
-      sessionInfo.lastActivityAt = new Date();
-      await logDebugEvent(args.sessionId, "terminated", {});
+    const test = await buildRustTest(args.program, args.testName, {
+      package: args.package,
     });
 
-    session.on("output", async (event) => {
-      sessionInfo.lastActivityAt = new Date();
-      await logDebugEvent(args.sessionId, "output", event);
+    const result = await launchSession({
+      sessionId: args.sessionId,
+      adapter: "lldb",
+      program: test.executable,
+      // Run only this test, on the main test thread, with output visible
+      args: [test.testName, "--exact", "--nocapture", "--test-threads=1"],
+      env: args.env,
+      cwd: test.cwd,
+      stopOnEntry: args.stopOnEntry,
+      enableLogging: args.enableLogging,
+      breakpoints: args.breakpoints,
     });
-
-    try {
-      await session.connect();
-      sessionInfo.state = SessionState.CONNECTED;
-      await logDebugEvent(args.sessionId, "connected", { adapter: args.adapter });
-      
-      await session.launch(args.program, {
-        args: args.args,
-        env: args.env,
-        cwd: args.cwd,
-        stopOnEntry: args.stopOnEntry,
-        noDebug: false,
-      });
-
-      if (!args.stopOnEntry) {
-        sessionInfo.state = SessionState.RUNNING;
-      }
-
-      await logDebugEvent(args.sessionId, "launched", { 
-        program: args.program,
-        args: args.args,
-        cwd: args.cwd
-      });
-
-      const result = [`Debug session ${args.sessionId} launched for ${args.program} (state: ${sessionInfo.state})`];
-      if (logFile) {
-        result.push(`Logging to: ${logFile}`);
-      }
-      return result.join("\n");
-    } catch (error) {
-      sessionInfo.state = SessionState.ERROR;
-      await logDebugEvent(args.sessionId, "error", { error: String(error) });
-      sessions.delete(args.sessionId);
-      throw error;
-    }
+    result.splice(1, 0, `Test: ${test.testName}`, `Built with cargo test --no-run from ${args.program}`);
+    return result.join("\n");
   },
 };
 
@@ -355,21 +500,8 @@ const setBreakpointsTool: ToolDef<z.ZodType> = {
       SessionState.RUNNING,
     ]);
 
-    // Clear existing breakpoints for this source
-    sessionInfo.breakpoints.delete(args.source);
-
-    // Create new breakpoint entries
-    const breakpoints: BreakpointInfo[] = args.lines.map((line: number, index: number) => ({
-      id: ++breakpointIdCounter,
-      source: args.source,
-      line,
-      condition: args.conditions?.[index],
-      hitCount: 0,
-      verified: false,
-      createdAt: new Date(),
-    }));
-
-    sessionInfo.breakpoints.set(args.source, breakpoints);
+    // Replace existing breakpoints for this source
+    const breakpoints = recordBreakpoints(sessionInfo, args.source, args.lines, args.conditions);
 
     await sessionInfo.session.setBreakpoints(args.source, args.lines, args.conditions);
     
@@ -1006,6 +1138,7 @@ class DAPMcpServer extends BaseMcpServer {
   protected setupHandlers(): void {
     // Core debugging tools
     this.registerTool(launchDebugSessionTool);
+    this.registerTool(launchTestTool);
     this.registerTool(attachDebugSessionTool);
     this.registerTool(setBreakpointsTool);
     this.registerTool(continueTool);
@@ -3,7 +3,11 @@
  */
 
 import { DAPClient } from "./dapClient.ts";
-import { resolveAdapter, getAdapterCapabilities } from "./adapterResolver.ts";
+import {
+  resolveAdapter,
+  getAdapterCapabilities,
+  launchesBeforeConfigurationDone,
+} from "./adapterResolver.ts";
 import { isTypeScriptFile, createTempJsFile } from "./typescriptSupport.ts";
 import type {
   InitializeRequestArguments,
@@ -19,6 +23,12 @@ import type {
   Scope,
 } from "./types.ts";
 
+export interface SourceBreakpoints {
+  source: string;
+  lines: number[];
+  conditions?: (string | undefined)[];
+}
+
 export interface DebugSessionOptions {
   /**
    * Debug adapter command (e.g., "node", "python", etc.)
@@ -64,6 +74,7 @@ export class DebugSession {
   // private initialized = false;
   private currentThreadId: number | null = null;
   private currentFrameId: number | null = null;
+  private initializedPromise: Promise<void> = Promise.resolve();
 
   constructor(private options: DebugSessionOptions) {
     this.client = new DAPClient();
@@ -103,15 +114,20 @@ export class DebugSession {
       ...capabilities,
     };
 
-    await this.client.initialize(initArgs);
-    
-    // Wait for initialized event
-    await new Promise<void>((resolve) => {
+    // Listen before initializing so an early initialized event is not missed
+    this.initializedPromise = new Promise<void>((resolve) => {
       this.client.once("initialized", () => {
         // this.initialized = true;
         resolve();
       });
     });
+
+    await this.client.initialize(initArgs);
+    
+    // Wait for initialized event, unless the adapter sends it after launch
+    if (!launchesBeforeConfigurationDone(this.options.adapter)) {
+      await this.initializedPromise;
+    }
   }
 
   /**
@@ -120,7 +136,7 @@ export class DebugSession {
   async setBreakpoints(
     source: string,
     lines: number[],
-    conditions?: string[]
+    conditions?: (string | undefined)[]
   ): Promise<void> {
     const args: SetBreakpointsArguments = {
       source: { path: source },
@@ -134,9 +150,29 @@ export class DebugSession {
   }
 
   /**
-   * Launch a program for debugging
+   * Launch a program for debugging.
+   * Breakpoints are set before configurationDone, so they apply from the start.
    */
-  async launch(program: string, args?: LaunchRequestArguments): Promise<void> {
+  async launch(
+    program: string,
+    args?: LaunchRequestArguments,
+    breakpoints: SourceBreakpoints[] = []
+  ): Promise<void> {
+    if (launchesBeforeConfigurationDone(this.options.adapter)) {
+      // lldb-dap creates the target on launch and only then sends initialized
+      const launched = this.client.sendRequest("launch", { program, ...args });
+      await this.initializedPromise;
+      for (const { source, lines, conditions } of breakpoints) {
+        await this.setBreakpoints(source, lines, conditions);
+      }
+      await this.client.sendRequest("configurationDone");
+      await launched;
+      return;
+    }
+
+    for (const { source, lines, conditions } of breakpoints) {
+      await this.setBreakpoints(source, lines, conditions);
+    }
     await this.client.sendRequest("configurationDone");
     
     // Handle TypeScript files
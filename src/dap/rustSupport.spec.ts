@@ -0,0 +1,26 @@
+import { describe, it, expect } from "vitest";
+import { matchTestName } from "./rustSupport.ts";
+
+describe("matchTestName", () => {
+  const tests = [
+    "tests::test_calculator",
+    "tests::test_greet",
+    "parser::tests::test_greet",
+  ];
+
+  it("should prefer an exact match", () => {
+    expect(matchTestName(tests, "tests::test_greet")).toEqual(["tests::test_greet"]);
+  });
+
+  it("should match by trailing path segments", () => {
+    expect(matchTestName(tests, "test_calculator")).toEqual(["tests::test_calculator"]);
+    expect(matchTestName(tests, "test_greet")).toEqual([
+      "tests::test_greet",
+      "parser::tests::test_greet",
+    ]);
+  });
+
+  it("should not match partial segment names", () => {
+    expect(matchTestName(tests, "calculator")).toEqual([]);
+  });
+});
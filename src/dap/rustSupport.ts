@@ -0,0 +1,119 @@
+/**
+ * Rust support for DAP debugging with the lldb adapter
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import { execFileSync } from "child_process";
+import { buildCargoBinary, buildCargoTests, listTests } from "../rust/cargo.ts";
+
+/**
+ * Check whether a program path points to a Cargo project (directory or Cargo.toml)
+ */
+export function isCargoProject(program: string): boolean {
+  if (path.basename(program) === "Cargo.toml") {
+    return fs.existsSync(program);
+  }
+  return (
+    fs.existsSync(program) &&
+    fs.statSync(program).isDirectory() &&
+    fs.existsSync(path.join(program, "Cargo.toml"))
+  );
+}
+
+/**
+ * Build a Cargo project and return the executable to launch
+ */
+export async function buildRustProgram(
+  program: string,
+  options: { bin?: string; release?: boolean } = {}
+): Promise<{ program: string; cwd: string }> {
+  const projectDir =
+    path.basename(program) === "Cargo.toml" ? path.dirname(program) : program;
+  const executable = await buildCargoBinary(projectDir, options);
+  return { program: executable, cwd: projectDir };
+}
+
+/**
+ * Find tests by full path, or by the last path segment(s) when there is no exact match
+ */
+export function matchTestName(tests: string[], testName: string): string[] {
+  if (tests.includes(testName)) {
+    return [testName];
+  }
+  return tests.filter((test) => test.endsWith(`::${testName}`));
+}
+
+/**
+ * Build the tests of a Cargo project and locate the executable containing testName
+ */
+export async function buildRustTest(
+  program: string,
+  testName: string,
+  options: { package?: string } = {}
+): Promise<{ executable: string; testName: string; cwd: string }> {
+  const projectDir =
+    path.basename(program) === "Cargo.toml" ? path.dirname(program) : program;
+  const artifacts = await buildCargoTests(projectDir, options);
+
+  const matches: { executable: string; testName: string; cwd: string; target: string }[] = [];
+  for (const artifact of artifacts) {
+    // cargo runs tests from the package directory
+    const cwd = path.dirname(artifact.manifestPath);
+    const tests = await listTests(artifact.executable, cwd);
+    for (const name of matchTestName(tests, testName)) {
+      matches.push({
+        executable: artifact.executable,
+        testName: name,
+        cwd,
+        target: `${artifact.targetName} (${artifact.kind.join(", ")})`,
+      });
+    }
+  }
+
+  if (matches.length === 0) {
+    throw new Error(
+      `No test named "${testName}" found in ${artifacts.map((a) => a.targetName).join(", ") || "any test target"}`
+    );
+  }
+  if (matches.length > 1) {
+    throw new Error(
+      `Test name "${testName}" is ambiguous:\n${matches.map((m) => `  ${m.testName} in ${m.target}`).join("\n")}`
+    );
+  }
+
+  const { executable, cwd } = matches[0];
+  return { executable, testName: matches[0].testName, cwd };
+}
+
+/**
+ * lldb commands that load the Rust pretty printers shipped with the toolchain,
+ * the same ones rust-lldb loads
+ */
+export function getRustLldbInitCommands(cwd?: string): string[] {
+  let sysroot: string;
+  try {
+    sysroot = execFileSync("rustc", ["--print", "sysroot"], {
+      cwd,
+      encoding: "utf-8",
+    }).trim();
+  } catch {
+    return [];
+  }
+
+  const etcDir = path.join(sysroot, "lib", "rustlib", "etc");
+  if (!fs.existsSync(path.join(etcDir, "lldb_lookup.py"))) {
+    return [];
+  }
+  return [
+    `command script import "${path.join(etcDir, "lldb_lookup.py")}"`,
+    `command source -s 0 "${path.join(etcDir, "lldb_commands")}"`,
+  ];
+}
+
+/**
+ * Convert an environment map to the KEY=VALUE list lldb-dap expects
+ */
+export function toLldbEnv(env?: Record<string, string>): string[] | undefined {
+  return env && Object.entries(env).map(([key, value]) => `${key}=${value}`);
+}
@@ -0,0 +1,357 @@
+/**
+ * Apply LSP WorkspaceEdits (text edits and file operations) to disk
+ */
+
+import {
+  existsSync,
+  mkdirSync,
+  readFileSync,
+  rmSync,
+  statSync,
+  writeFileSync,
+} from "fs";
+import path from "path";
+import { fileURLToPath } from "url";
+import {
+  CreateFile,
+  DeleteFile,
+  RenameFile,
+  TextDocumentEdit,
+  type TextEdit,
+  type WorkspaceEdit,
+} from "vscode-languageserver-types";
+import { applyTextEdits } from "../textUtils/applyTextEdits.ts";
+import { createUnifiedDiff } from "../textUtils/createUnifiedDiff.ts";
+
+export interface FileEditResult {
+  kind: "edit" | "create" | "rename" | "delete";
+  // Absolute path of the file (the new path for renames)
+  filePath: string;
+  // Absolute path before a rename
+  oldFilePath?: string;
+  oldContent: string;
+  newContent: string;
+  // Text edits applied to the file, in order
+  edits: TextEdit[];
+}
+
+export interface WorkspaceEditResult {
+  files: FileEditResult[];
+  // Unified diff of all changes, with paths relative to the root
+  diff: string;
+}
+
+export interface ApplyWorkspaceEditOptions {
+  // Compute the result without touching the file system
+  dryRun?: boolean;
+  // Directory used to relativize paths in the diff
+  root?: string;
+}
+
+/**
+ * Apply a WorkspaceEdit: its `documentChanges` (text edits, file creates, renames
+ * and deletes, in order) when present, and otherwise its `changes`. As in the LSP
+ * spec, `changes` is ignored when both are set.
+ * All edits are computed in memory first, so a failing edit leaves disk untouched.
+ */
+export function applyWorkspaceEdit(
+  edit: WorkspaceEdit,
+  options: ApplyWorkspaceEditOptions = {}
+): WorkspaceEditResult {
+  // Current content per path; null marks a deleted (or never existing) file
+  const contents = new Map<string, string | null>();
+  const originals = new Map<string, string | null>();
+  const textEdits = new Map<string, TextEdit[]>();
+  const renames: { from: string; to: string }[] = [];
+
+  const read = (filePath: string): string | null => {
+    if (!contents.has(filePath)) {
+      let content: string | null = null;
+      if (existsSync(filePath)) {
+        if (statSync(filePath).isDirectory()) {
+          throw new Error(`Directory resources are not supported: ${filePath}`);
+        }
+        content = readFileSync(filePath, "utf-8");
+      }
+      contents.set(filePath, content);
+      originals.set(filePath, content);
+    }
+    return contents.get(filePath)!;
+  };
+
+  const editFile = (uri: string, edits: TextEdit[]) => {
+    const filePath = fileURLToPath(uri);
+    const content = read(filePath);
+    if (content === null) {
+      throw new Error(`Cannot edit missing file: ${filePath}`);
+    }
+    contents.set(filePath, applyTextEdits(content, edits));
+    textEdits.set(filePath, [...(textEdits.get(filePath) ?? []), ...edits]);
+  };
+
+  if (!edit.documentChanges) {
+    for (const [uri, edits] of Object.entries(edit.changes ?? {})) {
+      editFile(uri, edits);
+    }
+  }
+
+  for (const change of edit.documentChanges ?? []) {
+    if (TextDocumentEdit.is(change)) {
+      editFile(change.textDocument.uri, change.edits);
+    } else if (CreateFile.is(change)) {
+      const filePath = fileURLToPath(change.uri);
+      if (read(filePath) !== null && !change.options?.overwrite) {
+        if (change.options?.ignoreIfExists) {
+          continue;
+        }
+        throw new Error(`File already exists: ${filePath}`);
+      }
+      contents.set(filePath, "");
+    } else if (RenameFile.is(change)) {
+      const from = fileURLToPath(change.oldUri);
+      const to = fileURLToPath(change.newUri);
+      const content = read(from);
+      if (content === null) {
+        throw new Error(`Cannot rename missing file: ${from}`);
+      }
+      if (read(to) !== null && !change.options?.overwrite) {
+        if (change.options?.ignoreIfExists) {
+          continue;
+        }
+        throw new Error(`File already exists: ${to}`);
+      }
+      contents.set(from, null);
+      contents.set(to, content);
+      textEdits.set(to, textEdits.get(from) ?? []);
+      textEdits.delete(from);
+      renames.push({ from, to });
+    } else if (DeleteFile.is(change)) {
+      const filePath = fileURLToPath(change.uri);
+      if (read(filePath) === null && !change.options?.ignoreIfNotExists) {
+        throw new Error(`Cannot delete missing file: ${filePath}`);
+      }
+      contents.set(filePath, null);
+    }
+  }
+
+  const files = collectFileResults(contents, originals, textEdits, renames);
+
+  if (!options.dryRun) {
+    for (const [filePath, content] of contents) {
+      if (content === originals.get(filePath)) {
+        continue;
+      }
+      if (content === null) {
+        rmSync(filePath, { force: true });
+      } else {
+        mkdirSync(path.dirname(filePath), { recursive: true });
+        writeFileSync(filePath, content, "utf-8");
+      }
+    }
+  }
+
+  const display = (filePath: string) =>
+    options.root ? path.relative(options.root, filePath) : filePath;
+  const diff = files
+    .map((file) =>
+      createUnifiedDiff(
+        file.kind === "create" ? "/dev/null" : display(file.oldFilePath ?? file.filePath),
+        file.kind === "delete" ? "/dev/null" : display(file.filePath),
+        file.oldContent,
+        file.newContent
+      ) ||
+      // Pure renames have no content diff
+      (file.kind === "rename"
+        ? `rename from ${display(file.oldFilePath!)}\nrename to ${display(file.filePath)}`
+        : "")
+    )
+    .filter(Boolean)
+    .join("\n");
+
+  return { files, diff };
+}
+
+function collectFileResults(
+  contents: Map<string, string | null>,
+  originals: Map<string, string | null>,
+  textEdits: Map<string, TextEdit[]>,
+  renames: { from: string; to: string }[]
+): FileEditResult[] {
+  const files: FileEditResult[] = [];
+  const handled = new Set<string>();
+
+  // Follow rename chains so a renamed-then-edited file is reported once
+  for (const { from } of renames) {
+    if (handled.has(from) || originals.get(from) === null) {
+      continue;
+    }
+    let to = from;
+    for (const rename of renames) {
+      if (rename.from === to) {
+        to = rename.to;
+      }
+    }
+    handled.add(from);
+    handled.add(to);
+    files.push({
+      kind: "rename",
+      filePath: to,
+      oldFilePath: from,
+      oldContent: originals.get(from) ?? "",
+      newContent: contents.get(to) ?? "",
+      edits: textEdits.get(to) ?? [],
+    });
+  }
+
+  for (const [filePath, content] of contents) {
+    const original = originals.get(filePath) ?? null;
+    if (handled.has(filePath) || content === original) {
+      continue;
+    }
+    files.push({
+      kind: original === null ? "create" : content === null ? "delete" : "edit",
+      filePath,
+      oldContent: original ?? "",
+      newContent: content ?? "",
+      edits: textEdits.get(filePath) ?? [],
+    });
+  }
+
+  return files;
+}
+
+/**
+ * Summarize applied file changes, one line per file
+ */
+export function formatWorkspaceEditSummary(
+  result: WorkspaceEditResult,
+  root: string
+): string {
+  return result.files
+    .map((file) => {
+      const filePath = path.relative(root, file.filePath);
+      return file.kind === "rename"
+        ? `rename: ${path.relative(root, file.oldFilePath!)} -> ${filePath}`
+        : `${file.kind}: ${filePath}`;
+    })
+    .join("\n");
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect, beforeEach, afterEach } = import.meta.vitest;
+  const { default: fs } = await import("fs/promises");
+  const { tmpdir } = await import("os");
+  const { pathToFileURL } = await import("url");
+
+  describe("applyWorkspaceEdit", () => {
+    let tmpDir: string;
+    const uri = (name: string) => pathToFileURL(path.join(tmpDir, name)).toString();
+    const range = (line: number, start: number, end: number) => ({
+      start: { line, character: start },
+      end: { line, character: end },
+    });
+
+    beforeEach(async () => {
+      tmpDir = await fs.mkdtemp(path.join(tmpdir(), "lsmcp-edit-"));
+      await fs.writeFile(path.join(tmpDir, "a.rs"), "fn foo() {}\nfn bar() {}\n");
+    });
+
+    afterEach(async () => {
+      await fs.rm(tmpDir, { recursive: true, force: true });
+    });
+
+    it("should apply text edits and report a diff", async () => {
+      const result = applyWorkspaceEdit(
+        { changes: { [uri("a.rs")]: [{ range: range(0, 3, 6), newText: "baz" }] } },
+        { root: tmpDir }
+      );
+
+      expect(await fs.readFile(path.join(tmpDir, "a.rs"), "utf-8")).toBe(
+        "fn baz() {}\nfn bar() {}\n"
+      );
+      expect(result.files).toHaveLength(1);
+      expect(result.files[0].edits).toHaveLength(1);
+      expect(result.diff).toContain("--- a/a.rs\n+++ b/a.rs");
+      expect(result.diff).toContain("-fn foo() {}\n+fn baz() {}");
+    });
+
+    it("should apply documentChanges instead of changes when both are set", async () => {
+      const result = applyWorkspaceEdit(
+        {
+          changes: { [uri("a.rs")]: [{ range: range(1, 3, 6), newText: "qux" }] },
+          documentChanges: [
+            {
+              textDocument: { uri: uri("a.rs"), version: null },
+              edits: [{ range: range(0, 3, 6), newText: "baz" }],
+            },
+          ],
+        },
+        { root: tmpDir }
+      );
+
+      expect(await fs.readFile(path.join(tmpDir, "a.rs"), "utf-8")).toBe(
+        "fn baz() {}\nfn bar() {}\n"
+      );
+      expect(result.diff).not.toContain("qux");
+    });
+
+    it("should not touch disk in dry-run mode", async () => {
+      const result = applyWorkspaceEdit(
+        { changes: { [uri("a.rs")]: [{ range: range(0, 3, 6), newText: "baz" }] } },
+        { dryRun: true, root: tmpDir }
+      );
+
+      expect(await fs.readFile(path.join(tmpDir, "a.rs"), "utf-8")).toBe(
+        "fn foo() {}\nfn bar() {}\n"
+      );
+      expect(result.diff).toContain("+fn baz() {}");
+    });
+
+    it("should apply file creates, renames and deletes in order", async () => {
+      await fs.writeFile(path.join(tmpDir, "old.rs"), "// old\n");
+      const result = applyWorkspaceEdit(
+        {
+          documentChanges: [
+            { kind: "create", uri: uri("sub/new.rs") },
+            {
+              textDocument: { uri: uri("sub/new.rs"), version: null },
+              edits: [{ range: range(0, 0, 0), newText: "pub fn moved() {}\n" }],
+            },
+            { kind: "rename", oldUri: uri("a.rs"), newUri: uri("b.rs") },
+            { kind: "delete", uri: uri("old.rs") },
+          ],
+        },
+        { root: tmpDir }
+      );
+
+      expect(await fs.readFile(path.join(tmpDir, "sub/new.rs"), "utf-8")).toBe(
+        "pub fn moved() {}\n"
+      );
+      expect(existsSync(path.join(tmpDir, "a.rs"))).toBe(false);
+      expect(existsSync(path.join(tmpDir, "b.rs"))).toBe(true);
+      expect(existsSync(path.join(tmpDir, "old.rs"))).toBe(false);
+      expect(formatWorkspaceEditSummary(result, tmpDir)).toBe(
+        ["rename: a.rs -> b.rs", "create: sub/new.rs", "delete: old.rs"].join("\n")
+      );
+      expect(result.diff).toContain("rename from a.rs\nrename to b.rs");
+      expect(result.diff).toContain("--- /dev/null\n+++ b/sub/new.rs");
+    });
+
+    it("should leave disk untouched when an edit fails", async () => {
+      expect(() =>
+        applyWorkspaceEdit({
+          documentChanges: [
+            {
+              textDocument: { uri: uri("a.rs"), version: null },
+              edits: [{ range: range(0, 3, 6), newText: "baz" }],
+            },
+            { kind: "create", uri: uri("a.rs") },
+          ],
+        })
+      ).toThrow("File already exists");
+      expect(await fs.readFile(path.join(tmpDir, "a.rs"), "utf-8")).toBe(
+        "fn foo() {}\nfn bar() {}\n"
+      );
+    });
+  });
+}
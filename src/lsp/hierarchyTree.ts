@@ -0,0 +1,215 @@
+/**
+ * Depth-limited trees for call and type hierarchies, expanded in one or both
+ * directions from the prepared items and rendered as indented text
+ */
+
+import { z } from "zod";
+import path from "path";
+import { fileURLToPath } from "url";
+import type { Range, SymbolKind } from "vscode-languageserver-types";
+import { getSymbolKindName } from "./tools/lspGetDocumentSymbols.ts";
+
+const DEFAULT_DEPTH = 2;
+const MAX_DEPTH = 5;
+
+// Fields shared by CallHierarchyItem and TypeHierarchyItem
+export interface HierarchyItem {
+  name: string;
+  kind: SymbolKind;
+  detail?: string;
+  uri: string;
+  selectionRange: Range;
+}
+
+// A related item with the edge data leading to it (e.g. call sites)
+export type HierarchyEdge<T extends HierarchyItem, E extends object> = E & { item: T };
+
+export type HierarchyNode<T extends HierarchyItem, E extends object = object> =
+  HierarchyEdge<T, E> & {
+    children: HierarchyNode<T, E>[];
+    // Already on the path from the root, so not expanded again
+    recursive?: boolean;
+  };
+
+export interface HierarchyDirection<T extends HierarchyItem, E extends object> {
+  name: string;
+  heading: string;
+  getRelated: (item: T) => Promise<HierarchyEdge<T, E>[]>;
+}
+
+/**
+ * Schema for the direction parameter: either direction or both
+ */
+export function directionSchema<D extends string>(
+  directions: [D, D],
+  description: string
+) {
+  return z
+    .enum([...directions, "both"] as [D, D, "both"])
+    .optional()
+    .describe(`${description} (default: both)`);
+}
+
+/**
+ * Schema for the depth parameter
+ * @param levels What one level is, e.g. "calls"
+ */
+export function depthSchema(levels: string) {
+  return z
+    .number()
+    .int()
+    .min(1)
+    .max(MAX_DEPTH)
+    .optional()
+    .describe(`Levels of ${levels} to expand (default: ${DEFAULT_DEPTH}, max: ${MAX_DEPTH})`);
+}
+
+function itemKey(item: HierarchyItem): string {
+  const { line, character } = item.selectionRange.start;
+  return `${item.uri}:${line}:${character}`;
+}
+
+/**
+ * Expand related items up to depth levels, stopping at recursion
+ */
+async function expandHierarchy<T extends HierarchyItem, E extends object>(
+  item: T,
+  getRelated: (item: T) => Promise<HierarchyEdge<T, E>[]>,
+  depth: number,
+  ancestors: Set<string>
+): Promise<HierarchyNode<T, E>[]> {
+  const nodes: HierarchyNode<T, E>[] = [];
+  for (const edge of await getRelated(item)) {
+    const key = itemKey(edge.item);
+    if (ancestors.has(key)) {
+      nodes.push({ ...edge, children: [], recursive: true });
+      continue;
+    }
+    const children =
+      depth > 1
+        ? await expandHierarchy(edge.item, getRelated, depth - 1, new Set([...ancestors, key]))
+        : [];
+    nodes.push({ ...edge, children });
+  }
+  return nodes;
+}
+
+export function formatHierarchyItem(root: string, item: HierarchyItem): string {
+  const filePath = path.relative(root, fileURLToPath(item.uri));
+  const detail = item.detail ? ` ${item.detail}` : "";
+  return `${item.name} [${getSymbolKindName(item.kind)}]${detail} - ${filePath}:${item.selectionRange.start.line + 1}`;
+}
+
+/**
+ * Format nodes as an indented tree, with extra lines per node (e.g. call sites)
+ * indented below it
+ */
+export function formatHierarchyTree<T extends HierarchyItem, E extends object>(
+  root: string,
+  nodes: HierarchyNode<T, E>[],
+  formatDetails: (node: HierarchyNode<T, E>) => string[] = () => [],
+  indent = "  "
+): string[] {
+  return nodes.flatMap((node) => [
+    `${indent}${formatHierarchyItem(root, node.item)}${node.recursive ? " (recursive)" : ""}`,
+    ...formatDetails(node).map((line) => `${indent}  ${line}`),
+    ...formatHierarchyTree(root, node.children, formatDetails, `${indent}  `),
+  ]);
+}
+
+/**
+ * Expand and format the hierarchy of each prepared item in the requested directions
+ */
+export async function formatHierarchies<T extends HierarchyItem, E extends object>(
+  root: string,
+  title: string,
+  items: T[],
+  directions: HierarchyDirection<T, E>[],
+  direction: string = "both",
+  depth: number = DEFAULT_DEPTH,
+  formatDetails?: (node: HierarchyNode<T, E>) => string[]
+): Promise<string> {
+  const selected = directions.filter(
+    ({ name }) => direction === "both" || direction === name
+  );
+  const sections: string[] = [];
+  for (const item of items) {
+    const lines = [`${title} for ${formatHierarchyItem(root, item)}`];
+    for (const { heading, getRelated } of selected) {
+      const nodes = await expandHierarchy(item, getRelated, depth, new Set([itemKey(item)]));
+      lines.push("", heading);
+      lines.push(
+        ...(nodes.length > 0 ? formatHierarchyTree(root, nodes, formatDetails) : ["  (none)"])
+      );
+    }
+    sections.push(lines.join("\n"));
+  }
+  return sections.join("\n\n");
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  const item = (name: string, line: number): HierarchyItem => ({
+    name,
+    kind: 11, // Interface
+    uri: "file:///work/src/shapes.ts",
+    selectionRange: { start: { line, character: 17 }, end: { line, character: 22 } },
+  });
+
+  describe("formatHierarchyTree", () => {
+    it("should indent children and details below their parent", () => {
+      const lines = formatHierarchyTree(
+        "/work",
+        [
+          {
+            item: item("Shape", 0),
+            note: "first",
+            children: [{ item: item("Polygon", 4), note: "", children: [], recursive: true }],
+          },
+        ],
+        (node) => (node.note ? [`note ${node.note}`] : [])
+      );
+
+      expect(lines).toEqual([
+        "  Shape [Interface] - src/shapes.ts:1",
+        "    note first",
+        "    Polygon [Interface] - src/shapes.ts:5 (recursive)",
+      ]);
+    });
+  });
+
+  describe("formatHierarchies", () => {
+    it("should expand to the requested depth and stop at cycles", async () => {
+      // A -> B -> A -> ...
+      const a = item("A", 0);
+      const b = item("B", 4);
+      const related = async (current: HierarchyItem) => [{ item: current === a ? b : a }];
+
+      const output = await formatHierarchies(
+        "/work",
+        "Type hierarchy",
+        [a],
+        [
+          { name: "subtypes", heading: "Subtypes:", getRelated: related },
+          { name: "supertypes", heading: "Supertypes:", getRelated: async () => [] },
+        ],
+        "both",
+        5
+      );
+
+      expect(output).toBe(
+        [
+          "Type hierarchy for A [Interface] - src/shapes.ts:1",
+          "",
+          "Subtypes:",
+          "  B [Interface] - src/shapes.ts:5",
+          "    A [Interface] - src/shapes.ts:1 (recursive)",
+          "",
+          "Supertypes:",
+          "  (none)",
+        ].join("\n")
+      );
+    });
+  });
+}
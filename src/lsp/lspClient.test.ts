@@ -1,8 +1,13 @@
 import { describe, it, expect, beforeEach, afterEach } from "vitest";
 import { createLSPClient } from "./lspClient.ts";
-import { readFileSync } from "fs";
-import { resolve } from "path";
-import { spawn } from "child_process";
+import type { LSPMessage } from "./lspTypes.ts";
+import { mkdtempSync, readFileSync, rmSync, writeFileSync } from "fs";
+import { join, resolve } from "path";
+import { tmpdir } from "os";
+import { pathToFileURL } from "url";
+import { EventEmitter } from "events";
+import { PassThrough } from "stream";
+import { spawn, type ChildProcess } from "child_process";
 
 describe("LSP Client Direct Integration", () => {
   const projectRoot = process.cwd();
@@ -237,3 +242,210 @@ describe("LSP Client Error Handling", () => {
     });
   });
 });
+
+/**
+ * In-process fake LSP server that answers initialize/shutdown and records
+ * everything the client sends
+ */
+function createFakeServer(capabilities: Record<string, unknown> = {}) {
+  const stdin = new PassThrough();
+  const stdout = new PassThrough();
+  const process = Object.assign(new EventEmitter(), {
+    stdin,
+    stdout,
+    stderr: new PassThrough(),
+    killed: false,
+    kill: () => true,
+  }) as unknown as ChildProcess;
+  const received: LSPMessage[] = [];
+
+  const send = (message: LSPMessage) => {
+    const content = JSON.stringify(message);
+    stdout.write(`Content-Length: ${Buffer.byteLength(content)}\r\n\r\n${content}`);
+  };
+
+  let buffer = "";
+  stdin.on("data", (data: Buffer) => {
+    buffer += data.toString();
+    while (true) {
+      const match = buffer.match(/^Content-Length: (\d+)\r\n\r\n/);
+      if (!match || buffer.length < match[0].length + Number(match[1])) {
+        return;
+      }
+      const body = buffer.substring(match[0].length, match[0].length + Number(match[1]));
+      buffer = buffer.substring(match[0].length + Number(match[1]));
+      const message = JSON.parse(body) as LSPMessage;
+      received.push(message);
+      if (message.method === "initialize" || message.method === "shutdown") {
+        send({ jsonrpc: "2.0", id: message.id, result: { capabilities } });
+      }
+    }
+  });
+
+  // Send a server -> client request and wait for the client's response
+  const request = async (id: number, method: string, params: unknown) => {
+    send({ jsonrpc: "2.0", id, method, params });
+    for (let i = 0; i < 50; i++) {
+      const response = received.find((m) => m.id === id && !m.method);
+      if (response) {
+        return response;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 10));
+    }
+    throw new Error(`No response to ${method}`);
+  };
+
+  const notify = (method: string, params: unknown) => {
+    send({ jsonrpc: "2.0", method, params });
+  };
+
+  return { process, received, request, notify };
+}
+
+describe("LSP Client Server Requests", () => {
+  let tmpDir: string;
+
+  beforeEach(() => {
+    tmpDir = mkdtempSync(join(tmpdir(), "lsmcp-client-"));
+  });
+
+  afterEach(() => {
+    rmSync(tmpDir, { recursive: true, force: true });
+  });
+
+  it("should apply workspace/applyEdit requests to disk", async () => {
+    const filePath = join(tmpDir, "a.rs");
+    writeFileSync(filePath, "fn foo() {}\n");
+    const uri = pathToFileURL(filePath).toString();
+    const server = createFakeServer();
+    const client = createLSPClient({ rootPath: tmpDir, process: server.process });
+    await client.start();
+    client.openDocument(uri, "fn foo() {}\n");
+
+    const response = await server.request(100, "workspace/applyEdit", {
+      edit: {
+        changes: {
+          [uri]: [
+            {
+              range: { start: { line: 0, character: 3 }, end: { line: 0, character: 6 } },
+              newText: "bar",
+            },
+          ],
+        },
+      },
+    });
+
+    expect(response.result).toEqual({ applied: true });
+    expect(readFileSync(filePath, "utf-8")).toBe("fn bar() {}\n");
+    // The open document is synced with the new content
+    const didChange = server.received.find((m) => m.method === "textDocument/didChange");
+    expect(didChange?.params).toMatchObject({ textDocument: { uri, version: 2 } });
+    await client.stop();
+  });
+
+  it("should answer workspace/configuration from settings", async () => {
+    const server = createFakeServer();
+    const client = createLSPClient({
+      rootPath: tmpDir,
+      process: server.process,
+      settings: { "rust-analyzer": { check: { command: "clippy" } } },
+    });
+    await client.start();
+
+    const response = await server.request(101, "workspace/configuration", {
+      items: [
+        { section: "rust-analyzer" },
+        { section: "rust-analyzer.check.command" },
+        { section: "unknown" },
+      ],
+    });
+
+    expect(response.result).toEqual([{ check: { command: "clippy" } }, "clippy", null]);
+    await client.stop();
+  });
+
+  it("should acknowledge progress and registration requests and reject unknown ones", async () => {
+    const server = createFakeServer();
+    const client = createLSPClient({ rootPath: tmpDir, process: server.process });
+    await client.start();
+
+    const progress = await server.request(102, "window/workDoneProgress/create", { token: "t" });
+    const register = await server.request(103, "client/registerCapability", { registrations: [] });
+    const unknown = await server.request(104, "custom/unknown", {});
+
+    expect(progress.result).toBeNull();
+    expect(progress.error).toBeUndefined();
+    expect(register.result).toBeNull();
+    expect(unknown.error?.code).toBe(-32601);
+    await client.stop();
+  });
+
+  it("should track $/progress and wait until the server is idle", async () => {
+    const server = createFakeServer();
+    const client = createLSPClient({ rootPath: tmpDir, process: server.process });
+    await client.start();
+
+    server.notify("$/progress", {
+      token: "indexing",
+      value: { kind: "begin", title: "Indexing", percentage: 0 },
+    });
+    server.notify("$/progress", {
+      token: "indexing",
+      value: { kind: "report", message: "3/10", percentage: 30 },
+    });
+    await new Promise((resolve) => setTimeout(resolve, 50));
+
+    expect(client.isIdle()).toBe(false);
+    expect(client.getProgress()).toMatchObject([
+      { token: "indexing", title: "Indexing", message: "3/10", percentage: 30 },
+    ]);
+    // Times out while indexing is still in progress
+    await expect(client.waitForIdle({ timeout: 100, settleTime: 10 })).resolves.toBe(false);
+
+    const idle = client.waitForIdle({ timeout: 1000, settleTime: 10 });
+    server.notify("$/progress", { token: "indexing", value: { kind: "end" } });
+    await expect(idle).resolves.toBe(true);
+    expect(client.getProgress()).toEqual([]);
+    await client.stop();
+  });
+
+  it("should register new workspace folders with servers accepting folder changes", async () => {
+    const server = createFakeServer({
+      workspace: { workspaceFolders: { supported: true, changeNotifications: true } },
+    });
+    const client = createLSPClient({ rootPath: tmpDir, process: server.process });
+    await client.start();
+
+    const member = join(tmpDir, "member");
+    expect(client.addWorkspaceFolder(member)).toBe(true);
+    expect(client.addWorkspaceFolder(member)).toBe(false);
+    expect(client.getWorkspaceFolders()).toHaveLength(2);
+
+    await new Promise((resolve) => setTimeout(resolve, 50));
+    const notifications = server.received.filter(
+      (m) => m.method === "workspace/didChangeWorkspaceFolders"
+    );
+    expect(notifications).toHaveLength(1);
+    expect(notifications[0].params).toEqual({
+      event: { added: [{ uri: pathToFileURL(member).toString(), name: "member" }], removed: [] },
+    });
+    // Servers asking for the folders see the registered one
+    const folders = await server.request(105, "workspace/workspaceFolders", null);
+    expect(folders.result).toHaveLength(2);
+    await client.stop();
+  });
+
+  it("should not record workspace folders the server cannot be told about", async () => {
+    const server = createFakeServer();
+    const client = createLSPClient({ rootPath: tmpDir, process: server.process });
+    await client.start();
+
+    expect(client.addWorkspaceFolder(join(tmpDir, "member"))).toBe(false);
+    expect(client.getWorkspaceFolders()).toHaveLength(1);
+    await new Promise((resolve) => setTimeout(resolve, 50));
+    expect(
+      server.received.some((m) => m.method === "workspace/didChangeWorkspaceFolders")
+    ).toBe(false);
+    await client.stop();
+  });
+});
@@ -1,6 +1,8 @@
 import { EventEmitter } from "events";
-import { Position, Location, Diagnostic, WorkspaceEdit, DocumentSymbol, SymbolInformation, CompletionItem, SignatureHelp, CodeAction, Command, Range, TextEdit, FormattingOptions } from "vscode-languageserver-types";
+import { Position, Location, Diagnostic, WorkspaceEdit, DocumentSymbol, SymbolInformation, CompletionItem, SignatureHelp, CodeAction, Command, Range, TextEdit, FormattingOptions, CallHierarchyItem, CallHierarchyIncomingCall, CallHierarchyOutgoingCall, LocationLink, TypeHierarchyItem, InlayHint, SelectionRange, FoldingRange } from "vscode-languageserver-types";
 import { ChildProcess } from "child_process";
+import { basename, resolve } from "path";
+import { pathToFileURL } from "url";
 import {
   LSPMessage,
   TextDocumentPositionParams,
@@ -26,8 +28,36 @@ import {
   SignatureHelpResult,
   CodeActionResult,
   FormattingResult,
+  PrepareCallHierarchyResult,
+  IncomingCallsResult,
+  OutgoingCallsResult,
+  ImplementationResult,
+  TypeDefinitionResult,
+  TypeHierarchyResult,
+  InlayHintResult,
+  SemanticTokensResult,
+  SelectionRangeResult,
+  FoldingRangeResult,
+  WillRenameFilesResult,
+  FileRename,
+  RenameFilesParams,
+  WorkspaceFolder,
+  ServerCapabilities,
+  DidChangeWorkspaceFoldersParams,
+  DiagnosticWaitOptions,
+  DocumentDiagnosticParams,
+  DocumentDiagnosticReport,
+  WorkspaceDiagnosticParams,
+  WorkspaceDiagnosticReport,
+  ServerRequestHandler,
+  ConfigurationParams,
+  IdleWaitOptions,
+  ProgressParams,
+  ServerStatus,
 } from "./lspTypes.ts";
+import { applyWorkspaceEdit, type WorkspaceEditResult } from "./applyWorkspaceEdit.ts";
 import { debug } from "../mcp/_mcplib.ts";
+import { decodeSemanticTokens, type SemanticToken } from "../textUtils/semanticTokens.ts";
 import { formatError, debugLog, ErrorContext } from "../mcp/utils/errorHandler.ts";
 
 // Re-export types for backward compatibility
@@ -43,6 +73,14 @@ export type {
 // Global state for active client
 let activeClient: LSPClient | null = null;
 
+// Defaults for waiting on textDocument/publishDiagnostics
+export const DEFAULT_DIAGNOSTICS_TIMEOUT = 5000;
+export const DEFAULT_DIAGNOSTICS_SETTLE_TIME = 300;
+
+// Defaults for waiting until the server has no work in progress
+export const DEFAULT_IDLE_TIMEOUT = 60000;
+export const DEFAULT_IDLE_SETTLE_TIME = 500;
+
 /**
  * Set the active LSP client (for testing purposes)
  * @param client The LSP client to set as active
@@ -64,12 +102,16 @@ export function getLSPClient(): LSPClient | undefined {
  * @param rootPath The root path of the project
  * @param process The LSP server process
  * @param languageId The language ID (default: "typescript")
+ * @param initializationOptions Server-specific initialization options
+ * @param settings Settings returned for workspace/configuration, keyed by section
  * @returns The initialized LSP client
  */
 export async function initialize(
   rootPath: string,
   process: ChildProcess,
-  languageId: string = "typescript"
+  languageId: string = "typescript",
+  initializationOptions?: unknown,
+  settings?: Record<string, unknown>
 ): Promise<LSPClient> {
   // Stop existing client if any
   if (activeClient) {
@@ -81,6 +123,8 @@ export async function initialize(
     rootPath,
     process,
     languageId,
+    initializationOptions,
+    settings,
   });
 
   // Start the client
@@ -110,6 +154,35 @@ export async function shutdown(): Promise<void> {
     activeClient = null;
   }
 }
+
+function toWorkspaceFolder(folderPath: string): WorkspaceFolder {
+  const absolutePath = resolve(folderPath);
+  return {
+    uri: pathToFileURL(absolutePath).toString(),
+    name: basename(absolutePath),
+  };
+}
+
+/**
+ * Look up a dotted configuration section (e.g. "rust-analyzer.check") in settings
+ */
+function getSettingsSection(
+  settings: Record<string, unknown> | undefined,
+  section?: string
+): unknown {
+  if (!section) {
+    return settings ?? null;
+  }
+  let value: unknown = settings;
+  for (const key of section.split(".")) {
+    if (!value || typeof value !== "object") {
+      return null;
+    }
+    value = (value as Record<string, unknown>)[key];
+  }
+  return value ?? null;
+}
+
 export function createLSPClient(config: LSPClientConfig): LSPClient {
   const state: LSPClientState = {
     process: config.process,
@@ -121,8 +194,30 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     eventEmitter: new EventEmitter(),
     rootPath: config.rootPath,
     languageId: config.languageId || "typescript",
+    workspaceFolders: [toWorkspaceFolder(config.rootPath)],
+    serverCapabilities: null,
+    requestHandlers: new Map(),
+    openDocuments: new Map(),
+    progress: new Map(),
+    serverStatus: null,
   };
 
+  // Default handlers for requests the server sends to the client
+  state.requestHandlers.set("workspace/applyEdit", (params) => {
+    const { edit, label } = params as ApplyWorkspaceEditParams;
+    return applyEdit(edit, label);
+  });
+  state.requestHandlers.set("workspace/configuration", (params) =>
+    (params as ConfigurationParams).items.map((item) =>
+      getSettingsSection(config.settings, item.section)
+    )
+  );
+  state.requestHandlers.set("workspace/workspaceFolders", () => state.workspaceFolders);
+  state.requestHandlers.set("window/workDoneProgress/create", () => null);
+  state.requestHandlers.set("window/showMessageRequest", () => null);
+  state.requestHandlers.set("client/registerCapability", () => null);
+  state.requestHandlers.set("client/unregisterCapability", () => null);
+
   function processBuffer(): void {
     while (state.buffer.length > 0) {
       if (state.contentLength === -1) {
@@ -182,11 +277,77 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
         // Store diagnostics for the file
         const params = message.params as PublishDiagnosticsParams;
         state.diagnostics.set(params.uri, params.diagnostics);
+        state.eventEmitter.emit("diagnostics", params);
+      } else if (message.method === "$/progress" && message.params) {
+        handleProgress(message.params as ProgressParams);
+      } else if (
+        message.method === "experimental/serverStatus" &&
+        message.params
+      ) {
+        state.serverStatus = message.params as ServerStatus;
+        state.eventEmitter.emit("progress");
+      }
+      if (message.id !== undefined) {
+        // Requests must be answered, or the server may wait forever
+        handleServerRequest(message).catch((error) => {
+          debug(`Failed to respond to ${message.method}:`, error);
+        });
       }
       state.eventEmitter.emit("message", message);
     }
   }
 
+  /**
+   * Track workDoneProgress begin/report/end and emit "progress"
+   */
+  function handleProgress({ token, value }: ProgressParams): void {
+    if (value.kind === "begin") {
+      state.progress.set(token, {
+        token,
+        title: value.title ?? "",
+        message: value.message,
+        percentage: value.percentage,
+        startedAt: Date.now(),
+      });
+    } else if (value.kind === "report") {
+      const progress = state.progress.get(token);
+      if (progress) {
+        progress.message = value.message ?? progress.message;
+        progress.percentage = value.percentage ?? progress.percentage;
+      }
+    } else if (value.kind === "end") {
+      state.progress.delete(token);
+    }
+    state.eventEmitter.emit("progress");
+  }
+
+  async function handleServerRequest(message: LSPMessage): Promise<void> {
+    const handler = state.requestHandlers.get(message.method!);
+    if (!handler) {
+      debug(`Unhandled server request: ${message.method}`);
+      sendMessage({
+        jsonrpc: "2.0",
+        id: message.id,
+        error: { code: -32601, message: `Unhandled method ${message.method}` },
+      });
+      return;
+    }
+
+    try {
+      const result = await handler(message.params);
+      sendMessage({ jsonrpc: "2.0", id: message.id, result: result ?? null });
+    } catch (error) {
+      sendMessage({
+        jsonrpc: "2.0",
+        id: message.id,
+        error: {
+          code: -32603,
+          message: error instanceof Error ? error.message : String(error),
+        },
+      });
+    }
+  }
+
   function sendMessage(message: LSPMessage): void {
     if (!state.process) {
       throw new Error("LSP server not started");
@@ -262,8 +423,33 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
       },
       locale: "en",
       rootPath: state.rootPath,
-      rootUri: `file://${state.rootPath}`,
+      rootUri: pathToFileURL(state.rootPath).toString(),
+      workspaceFolders: state.workspaceFolders,
       capabilities: {
+        workspace: {
+          workspaceFolders: true,
+          workspaceEdit: {
+            documentChanges: true,
+            resourceOperations: ["create", "rename", "delete"],
+          },
+          executeCommand: {
+            dynamicRegistration: false,
+          },
+          applyEdit: true,
+          // Only advertise pull configuration when there is something to answer with
+          configuration: config.settings !== undefined,
+          fileOperations: {
+            willRename: true,
+            didRename: true,
+          },
+        },
+        window: {
+          workDoneProgress: true,
+        },
+        experimental: {
+          // rust-analyzer reports indexing state via experimental/serverStatus
+          serverStatusNotification: true,
+        },
         textDocument: {
           synchronization: {
             dynamicRegistration: false,
@@ -289,17 +475,81 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
           documentSymbol: {
             hierarchicalDocumentSymbolSupport: true,
           },
+          diagnostic: {
+            dynamicRegistration: false,
+            relatedDocumentSupport: false,
+          },
+          codeAction: {
+            codeActionLiteralSupport: {
+              codeActionKind: {
+                valueSet: [
+                  "",
+                  "quickfix",
+                  "refactor",
+                  "refactor.extract",
+                  "refactor.inline",
+                  "refactor.rewrite",
+                  "source",
+                  "source.organizeImports",
+                  "source.fixAll",
+                ],
+              },
+            },
+            isPreferredSupport: true,
+            dataSupport: true,
+            resolveSupport: {
+              properties: ["edit", "command"],
+            },
+          },
+          callHierarchy: {
+            dynamicRegistration: false,
+          },
+          implementation: {
+            linkSupport: true,
+          },
+          typeDefinition: {
+            linkSupport: true,
+          },
+          typeHierarchy: {
+            dynamicRegistration: false,
+          },
+          inlayHint: {
+            dynamicRegistration: false,
+          },
+          selectionRange: {
+            dynamicRegistration: false,
+          },
+          foldingRange: {
+            dynamicRegistration: false,
+            lineFoldingOnly: false,
+          },
+          semanticTokens: {
+            dynamicRegistration: false,
+            requests: { full: true },
+            tokenTypes: [
+              "namespace", "type", "class", "enum", "interface", "struct",
+              "typeParameter", "parameter", "variable", "property", "enumMember",
+              "event", "function", "method", "macro", "keyword", "modifier",
+              "comment", "string", "number", "regexp", "operator", "decorator",
+            ],
+            tokenModifiers: [
+              "declaration", "definition", "readonly", "static", "deprecated",
+              "abstract", "async", "modification", "documentation", "defaultLibrary",
+            ],
+            formats: ["relative"],
+          },
         },
       },
-      // Add Deno-specific initialization options
-      initializationOptions: state.languageId === "deno" ? {
+      // Explicit options win; otherwise add Deno-specific initialization options
+      initializationOptions: config.initializationOptions ?? (state.languageId === "deno" ? {
         enable: true,
         lint: true,
         unstable: true,
-      } : undefined,
+      } : undefined),
     };
 
-    await sendRequest<InitializeResult>("initialize", initParams);
+    const result = await sendRequest<InitializeResult>("initialize", initParams);
+    state.serverCapabilities = result?.capabilities ?? null;
 
     // Send initialized notification
     sendNotification("initialized", {});
@@ -368,6 +618,7 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
       },
     };
     sendNotification("textDocument/didOpen", params);
+    state.openDocuments.set(uri, 1);
   }
 
   function closeDocument(uri: string): void {
@@ -377,6 +628,7 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
       },
     };
     sendNotification("textDocument/didClose", params);
+    state.openDocuments.delete(uri);
     // Also clear diagnostics for this document
     state.diagnostics.delete(uri);
   }
@@ -390,6 +642,7 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
       contentChanges: [{ text }],
     };
     sendNotification("textDocument/didChange", params);
+    state.openDocuments.set(uri, version);
   }
 
   async function findReferences(
@@ -462,6 +715,135 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     return state.diagnostics.get(uri) || [];
   }
 
+  /**
+   * Wait for the server to publish diagnostics for a document.
+   * Resolves once no new publishDiagnostics arrived for `settleTime` ms,
+   * or with the last known diagnostics after `timeout` ms.
+   * Call this before opening/updating the document to avoid missing the first publish.
+   */
+  function waitForDiagnostics(
+    uri: string,
+    options: DiagnosticWaitOptions = {}
+  ): Promise<Diagnostic[]> {
+    const timeout = options.timeout ?? DEFAULT_DIAGNOSTICS_TIMEOUT;
+    const settleTime = options.settleTime ?? DEFAULT_DIAGNOSTICS_SETTLE_TIME;
+
+    return new Promise<Diagnostic[]>((resolve) => {
+      let settleTimer: ReturnType<typeof setTimeout> | undefined;
+
+      const finish = () => {
+        clearTimeout(timeoutTimer);
+        clearTimeout(settleTimer);
+        state.eventEmitter.off("diagnostics", listener);
+        resolve(state.diagnostics.get(uri) || []);
+      };
+
+      const listener = (params: PublishDiagnosticsParams) => {
+        if (params.uri !== uri) {
+          return;
+        }
+        clearTimeout(settleTimer);
+        settleTimer = setTimeout(finish, settleTime);
+      };
+
+      const timeoutTimer = setTimeout(finish, timeout);
+      state.eventEmitter.on("diagnostics", listener);
+    });
+  }
+
+  function isIdle(): boolean {
+    return state.progress.size === 0 && (state.serverStatus?.quiescent ?? true);
+  }
+
+  /**
+   * Wait until no workDoneProgress is in flight (and the server reports
+   * itself quiescent) for settleTime
+   * @returns true if the server became idle, false on timeout
+   */
+  function waitForIdle(options: IdleWaitOptions = {}): Promise<boolean> {
+    const timeout = options.timeout ?? DEFAULT_IDLE_TIMEOUT;
+    const settleTime = options.settleTime ?? DEFAULT_IDLE_SETTLE_TIME;
+
+    return new Promise<boolean>((resolve) => {
+      let settleTimer: ReturnType<typeof setTimeout> | undefined;
+
+      const finish = () => {
+        clearTimeout(timeoutTimer);
+        clearTimeout(settleTimer);
+        state.eventEmitter.off("progress", check);
+        resolve(isIdle());
+      };
+
+      // Progress may start right after another ends, so restart the settle timer on every change
+      const check = () => {
+        clearTimeout(settleTimer);
+        if (isIdle()) {
+          settleTimer = setTimeout(finish, settleTime);
+        }
+      };
+
+      const timeoutTimer = setTimeout(finish, timeout);
+      state.eventEmitter.on("progress", check);
+      check();
+    });
+  }
+
+  /**
+   * Request diagnostics with textDocument/diagnostic (LSP 3.17)
+   * @returns Diagnostics or null if the server does not support pull diagnostics
+   */
+  async function pullDiagnostics(uri: string): Promise<Diagnostic[] | null> {
+    const provider = state.serverCapabilities?.diagnosticProvider;
+    if (!provider) {
+      return null;
+    }
+
+    const params: DocumentDiagnosticParams = {
+      textDocument: { uri },
+      identifier: provider.identifier,
+    };
+    const result = await sendRequest<DocumentDiagnosticReport | null>(
+      "textDocument/diagnostic",
+      params
+    );
+
+    if (result?.kind === "full") {
+      state.diagnostics.set(uri, result.items);
+      return result.items;
+    }
+    return state.diagnostics.get(uri) || [];
+  }
+
+  /**
+   * Request diagnostics for the whole workspace with workspace/diagnostic (LSP 3.17)
+   * @returns Diagnostics by document URI or null if the server does not support it
+   */
+  async function pullWorkspaceDiagnostics(): Promise<Map<string, Diagnostic[]> | null> {
+    const provider = state.serverCapabilities?.diagnosticProvider;
+    if (!provider?.workspaceDiagnostics) {
+      return null;
+    }
+
+    const params: WorkspaceDiagnosticParams = {
+      identifier: provider.identifier,
+      previousResultIds: [],
+    };
+    const result = await sendRequest<WorkspaceDiagnosticReport | null>(
+      "workspace/diagnostic",
+      params
+    );
+
+    const diagnostics = new Map<string, Diagnostic[]>();
+    for (const report of result?.items ?? []) {
+      const items = report.kind === "full"
+        ? report.items
+        : state.diagnostics.get(report.uri) || [];
+      state.diagnostics.set(report.uri, items);
+      diagnostics.set(report.uri, items);
+    }
+    return diagnostics;
+  }
+
   async function getDocumentSymbols(
     uri: string
   ): Promise<DocumentSymbol[] | SymbolInformation[]> {
@@ -538,15 +920,177 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     return result;
   }
 
+  async function prepareCallHierarchy(
+    uri: string,
+    position: Position
+  ): Promise<CallHierarchyItem[]> {
+    const params: TextDocumentPositionParams = {
+      textDocument: { uri },
+      position,
+    };
+    const result = await sendRequest<PrepareCallHierarchyResult>(
+      "textDocument/prepareCallHierarchy",
+      params
+    );
+    return result ?? [];
+  }
+
+  async function getIncomingCalls(
+    item: CallHierarchyItem
+  ): Promise<CallHierarchyIncomingCall[]> {
+    const result = await sendRequest<IncomingCallsResult>(
+      "callHierarchy/incomingCalls",
+      { item }
+    );
+    return result ?? [];
+  }
+
+  async function getOutgoingCalls(
+    item: CallHierarchyItem
+  ): Promise<CallHierarchyOutgoingCall[]> {
+    const result = await sendRequest<OutgoingCallsResult>(
+      "callHierarchy/outgoingCalls",
+      { item }
+    );
+    return result ?? [];
+  }
+
+  async function getImplementation(
+    uri: string,
+    position: Position
+  ): Promise<Location[] | LocationLink[]> {
+    const params: TextDocumentPositionParams = {
+      textDocument: { uri },
+      position,
+    };
+    const result = await sendRequest<ImplementationResult>(
+      "textDocument/implementation",
+      params
+    );
+    return result ? (Array.isArray(result) ? result : [result]) : [];
+  }
+
+  async function getTypeDefinition(
+    uri: string,
+    position: Position
+  ): Promise<Location[] | LocationLink[]> {
+    const params: TextDocumentPositionParams = {
+      textDocument: { uri },
+      position,
+    };
+    const result = await sendRequest<TypeDefinitionResult>(
+      "textDocument/typeDefinition",
+      params
+    );
+    return result ? (Array.isArray(result) ? result : [result]) : [];
+  }
+
+  async function prepareTypeHierarchy(
+    uri: string,
+    position: Position
+  ): Promise<TypeHierarchyItem[]> {
+    const params: TextDocumentPositionParams = {
+      textDocument: { uri },
+      position,
+    };
+    const result = await sendRequest<TypeHierarchyResult>(
+      "textDocument/prepareTypeHierarchy",
+      params
+    );
+    return result ?? [];
+  }
+
+  async function getSupertypes(item: TypeHierarchyItem): Promise<TypeHierarchyItem[]> {
+    const result = await sendRequest<TypeHierarchyResult>(
+      "typeHierarchy/supertypes",
+      { item }
+    );
+    return result ?? [];
+  }
+
+  async function getSubtypes(item: TypeHierarchyItem): Promise<TypeHierarchyItem[]> {
+    const result = await sendRequest<TypeHierarchyResult>(
+      "typeHierarchy/subtypes",
+      { item }
+    );
+    return result ?? [];
+  }
+
+  async function getInlayHints(uri: string, range: Range): Promise<InlayHint[]> {
+    const result = await sendRequest<InlayHintResult>("textDocument/inlayHint", {
+      textDocument: { uri },
+      range,
+    });
+    return result ?? [];
+  }
+
+  async function getSemanticTokens(uri: string): Promise<SemanticToken[] | null> {
+    const provider = state.serverCapabilities?.semanticTokensProvider;
+    if (!provider?.full) {
+      return null;
+    }
+
+    try {
+      const result = await sendRequest<SemanticTokensResult>(
+        "textDocument/semanticTokens/full",
+        { textDocument: { uri } }
+      );
+      return result ? decodeSemanticTokens(result.data, provider.legend) : null;
+    } catch {
+      // Tokens only refine position lookups, so a failed request is not fatal
+      return null;
+    }
+  }
+
+  async function getSelectionRanges(
+    uri: string,
+    positions: Position[]
+  ): Promise<SelectionRange[]> {
+    const result = await sendRequest<SelectionRangeResult>(
+      "textDocument/selectionRange",
+      { textDocument: { uri }, positions }
+    );
+    return result ?? [];
+  }
+
+  async function getFoldingRanges(uri: string): Promise<FoldingRange[]> {
+    const result = await sendRequest<FoldingRangeResult>(
+      "textDocument/foldingRange",
+      { textDocument: { uri } }
+    );
+    return result ?? [];
+  }
+
+  /**
+   * Ask the server for edits (imports, module declarations, ...) to apply before
+   * files are renamed. Returns null when the server does not handle renames.
+   */
+  async function willRenameFiles(files: FileRename[]): Promise<WorkspaceEdit | null> {
+    if (!state.serverCapabilities?.workspace?.fileOperations?.willRename) {
+      return null;
+    }
+    const params: RenameFilesParams = { files };
+    return await sendRequest<WillRenameFilesResult>("workspace/willRenameFiles", params);
+  }
+
+  function didRenameFiles(files: FileRename[]): void {
+    if (!state.serverCapabilities?.workspace?.fileOperations?.didRename) {
+      return;
+    }
+    const params: RenameFilesParams = { files };
+    sendNotification("workspace/didRenameFiles", params);
+  }
+
   async function getCodeActions(
     uri: string,
     range: Range,
-    context?: { diagnostics?: Diagnostic[] }
+    context?: { diagnostics?: Diagnostic[]; only?: string[] }
   ): Promise<(Command | CodeAction)[]> {
     const params = {
       textDocument: { uri },
       range,
-      context: context || { diagnostics: [] },
+      // Servers such as typescript-language-server only return source actions listed in `only`
+      context: { diagnostics: [], ...context },
     };
     const result = await sendRequest<CodeActionResult>(
       "textDocument/codeAction",
@@ -555,6 +1099,26 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     return result ?? [];
   }
 
+  async function resolveCodeAction(action: CodeAction): Promise<CodeAction> {
+    // Only servers advertising resolveProvider accept codeAction/resolve
+    const provider = state.serverCapabilities?.codeActionProvider;
+    if (typeof provider !== "object" || !provider.resolveProvider) {
+      return action;
+    }
+    const result = await sendRequest<CodeAction>("codeAction/resolve", action);
+    return result ?? action;
+  }
+
+  async function executeCommand(
+    command: string,
+    args?: unknown[]
+  ): Promise<unknown> {
+    return sendRequest("workspace/executeCommand", {
+      command,
+      arguments: args,
+    });
+  }
+
   async function formatDocument(
     uri: string,
     options: FormattingOptions
@@ -638,19 +1202,63 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     }
   }
 
+  /**
+   * Apply a WorkspaceEdit to disk on the client side. This also serves
+   * server-initiated workspace/applyEdit requests (e.g. from executeCommand).
+   * Emits "applyEdit" with the label and the applied changes.
+   */
   async function applyEdit(
     edit: WorkspaceEdit,
     label?: string
   ): Promise<ApplyWorkspaceEditResponse> {
-    const params: ApplyWorkspaceEditParams = {
-      edit,
-      label,
+    let result: WorkspaceEditResult;
+    try {
+      result = applyWorkspaceEdit(edit, { root: state.rootPath });
+    } catch (error) {
+      return {
+        applied: false,
+        failureReason: error instanceof Error ? error.message : String(error),
+      };
+    }
+
+    // Keep documents the server has open in sync with the new contents
+    for (const file of result.files) {
+      const uri = pathToFileURL(file.filePath).toString();
+      const version = state.openDocuments.get(uri);
+      if (version !== undefined && file.kind !== "delete") {
+        updateDocument(uri, file.newContent, version + 1);
+      }
+    }
+
+    state.eventEmitter.emit("applyEdit", { label, result });
+    return { applied: true };
+  }
+
+  /**
+   * Register a folder with the server via workspace/didChangeWorkspaceFolders
+   * @returns true if the server was told about the folder
+   */
+  function addWorkspaceFolder(folderPath: string): boolean {
+    const folder = toWorkspaceFolder(folderPath);
+    if (state.workspaceFolders.some((f) => f.uri === folder.uri)) {
+      return false;
+    }
+
+    // Only servers that accept folder changes learn about new folders; others
+    // keep serving the initial root
+    const changeNotifications =
+      state.serverCapabilities?.workspace?.workspaceFolders?.changeNotifications;
+    if (!changeNotifications) {
+      debug(`LSP server does not accept workspace folder changes, ignoring ${folderPath}`);
+      return false;
+    }
+
+    state.workspaceFolders.push(folder);
+    const params: DidChangeWorkspaceFoldersParams = {
+      event: { added: [folder], removed: [] },
     };
-    const result = await sendRequest<ApplyWorkspaceEditResponse>(
-      "workspace/applyEdit",
-      params
-    );
-    return result ?? { applied: false, failureReason: "No response from server" };
+    sendNotification("workspace/didChangeWorkspaceFolders", params);
+    return true;
   }
 
   async function stop(): Promise<void> {
@@ -688,20 +1296,51 @@ export function createLSPClient(config: LSPClientConfig): LSPClient {
     getDefinition,
     getHover,
     getDiagnostics,
+    waitForDiagnostics,
+    waitForIdle,
+    isIdle,
+    getProgress: () => [...state.progress.values()],
+    getServerStatus: () => state.serverStatus,
+    pullDiagnostics,
+    pullWorkspaceDiagnostics,
     getDocumentSymbols,
     getWorkspaceSymbols,
     getCompletion,
     resolveCompletionItem,
     getSignatureHelp,
     getCodeActions,
+    resolveCodeAction,
+    executeCommand,
     formatDocument,
     formatRange,
     prepareRename,
     rename,
+    prepareCallHierarchy,
+    getIncomingCalls,
+    getOutgoingCalls,
+    getImplementation,
+    getTypeDefinition,
+    prepareTypeHierarchy,
+    getSupertypes,
+    getSubtypes,
+    getInlayHints,
+    getSemanticTokens,
+    getSelectionRanges,
+    getFoldingRanges,
+    willRenameFiles,
+    didRenameFiles,
     applyEdit,
+    addWorkspaceFolder,
+    getWorkspaceFolders: () => [...state.workspaceFolders],
+    getServerCapabilities: () => state.serverCapabilities,
     sendRequest,
+    onRequest: (method: string, handler: ServerRequestHandler) => {
+      state.requestHandlers.set(method, handler);
+    },
     on: (event: string, listener: (...args: unknown[]) => void) =>
       state.eventEmitter.on(event, listener),
+    off: (event: string, listener: (...args: unknown[]) => void) =>
+      state.eventEmitter.off(event, listener),
     emit: (event: string, ...args: unknown[]) =>
       state.eventEmitter.emit(event, ...args),
   };
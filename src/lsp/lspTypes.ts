@@ -19,8 +19,17 @@ import {
   CodeAction,
   Command,
   FormattingOptions,
+  CallHierarchyItem,
+  CallHierarchyIncomingCall,
+  CallHierarchyOutgoingCall,
+  LocationLink,
+  TypeHierarchyItem,
+  InlayHint,
+  SelectionRange,
+  FoldingRange,
 } from "vscode-languageserver-types";
 import { ChildProcess } from "child_process";
+import type { SemanticToken, SemanticTokensLegend } from "../textUtils/semanticTokens.ts";
 import { EventEmitter } from "events";
 
 // LSP Message types
@@ -52,6 +61,70 @@ export interface PublishDiagnosticsParams {
   diagnostics: Diagnostic[];
 }
 
+// LSP 3.17 pull diagnostics
+export interface DocumentDiagnosticParams {
+  textDocument: TextDocumentIdentifier;
+  identifier?: string;
+  previousResultId?: string;
+}
+
+export type DocumentDiagnosticReport =
+  | { kind: "full"; resultId?: string; items: Diagnostic[] }
+  | { kind: "unchanged"; resultId: string };
+
+export interface WorkspaceDiagnosticParams {
+  identifier?: string;
+  previousResultIds: { uri: DocumentUri; value: string }[];
+}
+
+export type WorkspaceDocumentDiagnosticReport = DocumentDiagnosticReport & {
+  uri: DocumentUri;
+  version: integer | null;
+};
+
+export interface WorkspaceDiagnosticReport {
+  items: WorkspaceDocumentDiagnosticReport[];
+}
+
+export interface DiagnosticWaitOptions {
+  timeout?: number; // Maximum time to wait in ms
+  settleTime?: number; // Quiet period after the last publish in ms
+}
+
+export interface IdleWaitOptions {
+  timeout?: number; // Maximum time to wait in ms
+  settleTime?: number; // How long the server must stay idle in ms
+}
+
+export type ProgressToken = number | string;
+
+export interface ProgressParams {
+  token: ProgressToken;
+  value: {
+    kind: "begin" | "report" | "end";
+    title?: string;
+    message?: string;
+    percentage?: number;
+    cancellable?: boolean;
+  };
+}
+
+// An in-flight workDoneProgress reported via $/progress
+export interface WorkDoneProgress {
+  token: ProgressToken;
+  title: string;
+  message?: string;
+  percentage?: number;
+  startedAt: number;
+}
+
+// rust-analyzer's experimental/serverStatus notification
+export interface ServerStatus {
+  health: "ok" | "warning" | "error";
+  quiescent: boolean;
+  message?: string;
+}
+
 export interface ReferenceContext {
   includeDeclaration: boolean;
 }
@@ -61,6 +134,27 @@ export interface ReferenceParams extends TextDocumentPositionParams {
 }
 
 export interface ClientCapabilities {
+  workspace?: {
+    workspaceFolders?: boolean;
+    workspaceEdit?: {
+      documentChanges?: boolean;
+      resourceOperations?: ("create" | "rename" | "delete")[];
+    };
+    executeCommand?: {
+      dynamicRegistration?: boolean;
+    };
+    applyEdit?: boolean;
+    configuration?: boolean;
+    fileOperations?: {
+      willRename?: boolean;
+      didRename?: boolean;
+    };
+  };
+  window?: {
+    workDoneProgress?: boolean;
+    showMessage?: Record<string, unknown>;
+  };
+  experimental?: Record<string, unknown>;
   textDocument?: {
     synchronization?: {
       dynamicRegistration?: boolean;
@@ -86,9 +180,55 @@ export interface ClientCapabilities {
     documentSymbol?: {
       hierarchicalDocumentSymbolSupport?: boolean;
     };
+    diagnostic?: {
+      dynamicRegistration?: boolean;
+      relatedDocumentSupport?: boolean;
+    };
+    codeAction?: {
+      codeActionLiteralSupport?: {
+        codeActionKind: { valueSet: string[] };
+      };
+      isPreferredSupport?: boolean;
+      dataSupport?: boolean;
+      resolveSupport?: { properties: string[] };
+    };
+    callHierarchy?: {
+      dynamicRegistration?: boolean;
+    };
+    implementation?: {
+      linkSupport?: boolean;
+    };
+    typeDefinition?: {
+      linkSupport?: boolean;
+    };
+    typeHierarchy?: {
+      dynamicRegistration?: boolean;
+    };
+    inlayHint?: {
+      dynamicRegistration?: boolean;
+    };
+    selectionRange?: {
+      dynamicRegistration?: boolean;
+    };
+    foldingRange?: {
+      dynamicRegistration?: boolean;
+      lineFoldingOnly?: boolean;
+    };
+    semanticTokens?: {
+      dynamicRegistration?: boolean;
+      requests: { full?: boolean };
+      tokenTypes: string[];
+      tokenModifiers: string[];
+      formats: "relative"[];
+    };
   };
 }
 
+export interface WorkspaceFolder {
+  uri: DocumentUri;
+  name: string;
+}
+
 export interface InitializeParams {
   processId: number | null;
   clientInfo?: {
@@ -98,18 +238,49 @@ export interface InitializeParams {
   locale?: string;
   rootPath?: string | null;
   rootUri: DocumentUri | null;
+  workspaceFolders?: WorkspaceFolder[] | null;
   capabilities: ClientCapabilities;
   initializationOptions?: any;
 }
 
-export interface InitializeResult {
-  capabilities: {
-    textDocumentSync?: number;
-    hoverProvider?: boolean;
-    definitionProvider?: boolean;
-    referencesProvider?: boolean;
+export interface ServerCapabilities {
+  textDocumentSync?: number;
+  hoverProvider?: boolean;
+  definitionProvider?: boolean;
+  referencesProvider?: boolean;
+  codeActionProvider?: boolean | {
+    codeActionKinds?: string[];
+    resolveProvider?: boolean;
+  };
+  executeCommandProvider?: {
+    commands: string[];
+  };
+  diagnosticProvider?: {
+    identifier?: string;
+    interFileDependencies: boolean;
+    workspaceDiagnostics: boolean;
+  };
+  semanticTokensProvider?: {
+    legend: SemanticTokensLegend;
+    full?: boolean | { delta?: boolean };
+  };
+  workspace?: {
+    workspaceFolders?: {
+      supported?: boolean;
+      changeNotifications?: string | boolean;
+    };
+    // Registration options with file filters; presence means supported
+    fileOperations?: {
+      willRename?: unknown;
+      didRename?: unknown;
+    };
     [key: string]: unknown;
   };
+  [key: string]: unknown;
+}
+
+export interface InitializeResult {
+  capabilities: ServerCapabilities;
   serverInfo?: {
     name: string;
     version?: string;
@@ -144,6 +315,24 @@ export interface DidCloseTextDocumentParams {
   textDocument: TextDocumentIdentifier;
 }
 
+export interface WorkspaceFoldersChangeEvent {
+  added: WorkspaceFolder[];
+  removed: WorkspaceFolder[];
+}
+
+export interface DidChangeWorkspaceFoldersParams {
+  event: WorkspaceFoldersChangeEvent;
+}
+
+export interface FileRename {
+  oldUri: DocumentUri;
+  newUri: DocumentUri;
+}
+
+export interface RenameFilesParams {
+  files: FileRename[];
+}
+
 // Workspace Edit types
 export interface ApplyWorkspaceEditParams {
   label?: string;
@@ -166,6 +355,17 @@ export type CompletionResult = CompletionItem[] | CompletionList | null;
 export type SignatureHelpResult = SignatureHelp | null;
 export type CodeActionResult = (Command | CodeAction)[] | null;
 export type FormattingResult = TextEdit[] | null;
+export type PrepareCallHierarchyResult = CallHierarchyItem[] | null;
+export type IncomingCallsResult = CallHierarchyIncomingCall[] | null;
+export type OutgoingCallsResult = CallHierarchyOutgoingCall[] | null;
+export type ImplementationResult = Location | Location[] | LocationLink[] | null;
+export type TypeDefinitionResult = Location | Location[] | LocationLink[] | null;
+export type TypeHierarchyResult = TypeHierarchyItem[] | null;
+export type InlayHintResult = InlayHint[] | null;
+export type SemanticTokensResult = { resultId?: string; data: number[] } | null;
+export type SelectionRangeResult = SelectionRange[] | null;
+export type FoldingRangeResult = FoldingRange[] | null;
+export type WillRenameFilesResult = WorkspaceEdit | null;
 
 // Hover contents types
 export type HoverContents =
@@ -184,6 +384,25 @@ export interface LSPClientState {
   eventEmitter: EventEmitter;
   rootPath: string;
   languageId: string;
+  workspaceFolders: WorkspaceFolder[];
+  serverCapabilities: ServerCapabilities | null;
+  // Handlers for server -> client requests, keyed by method
+  requestHandlers: Map<string, ServerRequestHandler>;
+  // Versions of documents currently open in the server, keyed by URI
+  openDocuments: Map<string, number>;
+  progress: Map<ProgressToken, WorkDoneProgress>;
+  serverStatus: ServerStatus | null;
+}
+
+export type ServerRequestHandler = (params: unknown) => unknown | Promise<unknown>;
+
+export interface ConfigurationItem {
+  scopeUri?: string;
+  section?: string;
+}
+
+export interface ConfigurationParams {
+  items: ConfigurationItem[];
 }
 
 export interface LSPClientConfig {
@@ -192,6 +411,8 @@ export interface LSPClientConfig {
   languageId?: string; // Default: "typescript"
   clientName?: string; // Default: "lsp-client"
   clientVersion?: string; // Default: "0.1.0"
+  initializationOptions?: unknown; // Server-specific options sent with initialize
+  settings?: Record<string, unknown>; // Answers workspace/configuration, keyed by section
 }
 
 export type LSPClient = {
@@ -207,18 +428,46 @@ export type LSPClient = {
   ) => Promise<Location | Location[]>;
   getHover: (uri: string, position: Position) => Promise<HoverResult>;
   getDiagnostics: (uri: string) => Diagnostic[];
+  waitForDiagnostics: (uri: string, options?: DiagnosticWaitOptions) => Promise<Diagnostic[]>;
+  waitForIdle: (options?: IdleWaitOptions) => Promise<boolean>;
+  isIdle: () => boolean;
+  getProgress: () => WorkDoneProgress[];
+  getServerStatus: () => ServerStatus | null;
+  pullDiagnostics: (uri: string) => Promise<Diagnostic[] | null>;
+  pullWorkspaceDiagnostics: () => Promise<Map<string, Diagnostic[]> | null>;
   getDocumentSymbols: (uri: string) => Promise<DocumentSymbol[] | SymbolInformation[]>;
   getWorkspaceSymbols: (query: string) => Promise<SymbolInformation[]>;
   getCompletion: (uri: string, position: Position) => Promise<CompletionItem[]>;
   resolveCompletionItem: (item: CompletionItem) => Promise<CompletionItem>;
   getSignatureHelp: (uri: string, position: Position) => Promise<SignatureHelp | null>;
-  getCodeActions: (uri: string, range: Range, context?: { diagnostics?: Diagnostic[] }) => Promise<(Command | CodeAction)[]>;
+  getCodeActions: (uri: string, range: Range, context?: { diagnostics?: Diagnostic[]; only?: string[] }) => Promise<(Command | CodeAction)[]>;
+  resolveCodeAction: (action: CodeAction) => Promise<CodeAction>;
+  executeCommand: (command: string, args?: unknown[]) => Promise<unknown>;
   formatDocument: (uri: string, options: FormattingOptions) => Promise<TextEdit[]>;
   formatRange: (uri: string, range: Range, options: FormattingOptions) => Promise<TextEdit[]>;
   prepareRename: (uri: string, position: Position) => Promise<Range | null>;
   rename: (uri: string, position: Position, newName: string) => Promise<WorkspaceEdit | null>;
+  prepareCallHierarchy: (uri: string, position: Position) => Promise<CallHierarchyItem[]>;
+  getIncomingCalls: (item: CallHierarchyItem) => Promise<CallHierarchyIncomingCall[]>;
+  getOutgoingCalls: (item: CallHierarchyItem) => Promise<CallHierarchyOutgoingCall[]>;
+  getImplementation: (uri: string, position: Position) => Promise<Location[] | LocationLink[]>;
+  getTypeDefinition: (uri: string, position: Position) => Promise<Location[] | LocationLink[]>;
+  prepareTypeHierarchy: (uri: string, position: Position) => Promise<TypeHierarchyItem[]>;
+  getSupertypes: (item: TypeHierarchyItem) => Promise<TypeHierarchyItem[]>;
+  getSubtypes: (item: TypeHierarchyItem) => Promise<TypeHierarchyItem[]>;
+  getInlayHints: (uri: string, range: Range) => Promise<InlayHint[]>;
+  getSemanticTokens: (uri: string) => Promise<SemanticToken[] | null>;
+  getSelectionRanges: (uri: string, positions: Position[]) => Promise<SelectionRange[]>;
+  getFoldingRanges: (uri: string) => Promise<FoldingRange[]>;
+  willRenameFiles: (files: FileRename[]) => Promise<WorkspaceEdit | null>;
+  didRenameFiles: (files: FileRename[]) => void;
   applyEdit: (edit: WorkspaceEdit, label?: string) => Promise<ApplyWorkspaceEditResponse>;
+  addWorkspaceFolder: (folderPath: string) => boolean;
+  getWorkspaceFolders: () => WorkspaceFolder[];
+  getServerCapabilities: () => ServerCapabilities | null;
   sendRequest: <T = unknown>(method: string, params?: unknown) => Promise<T>;
+  onRequest: (method: string, handler: ServerRequestHandler) => void;
   on: (event: string, listener: (...args: unknown[]) => void) => void;
+  off: (event: string, listener: (...args: unknown[]) => void) => void;
   emit: (event: string, ...args: unknown[]) => boolean;
 };
\ No newline at end of file
@@ -0,0 +1,145 @@
+/**
+ * file:line formatting for navigation results (implementations, parent
+ * modules, ...), in the same format as lsmcp_get_definitions
+ */
+
+import { readFileSync } from "fs";
+import path from "path";
+import { fileURLToPath } from "url";
+import type { Location, LocationLink } from "vscode-languageserver-types";
+
+export interface NavigationTarget {
+  filePath: string;
+  // 0-based position
+  line: number;
+  character: number;
+  // Shown after the location; defaults to the identifier at the position
+  label?: string;
+}
+
+const CONTEXT_LINES = 2;
+
+/**
+ * Normalize Location / LocationLink results to navigation targets
+ */
+export function toNavigationTargets(
+  result: Location | Location[] | LocationLink[] | null | undefined
+): NavigationTarget[] {
+  const locations = result ? (Array.isArray(result) ? result : [result]) : [];
+  return locations.map((location) => {
+    const [uri, range] =
+      "targetUri" in location
+        ? [location.targetUri, location.targetSelectionRange]
+        : [location.uri, location.range];
+    return {
+      filePath: fileURLToPath(uri),
+      line: range.start.line,
+      character: range.start.character,
+    };
+  });
+}
+
+function identifierAt(lineText: string, character: number): string | undefined {
+  const identifierPattern = /[a-zA-Z_][a-zA-Z0-9_]*/g;
+  let match;
+  while ((match = identifierPattern.exec(lineText)) !== null) {
+    if (match.index <= character && character < match.index + match[0].length) {
+      return match[0];
+    }
+  }
+  return undefined;
+}
+
+/**
+ * Format targets as `file:line:column - name` followed by a preview with context
+ */
+export function formatNavigationTargets(
+  root: string,
+  message: string,
+  targets: NavigationTarget[]
+): string {
+  const messages = [message];
+
+  for (const target of targets) {
+    let lines: string[] = [];
+    try {
+      lines = readFileSync(target.filePath, "utf-8").split("\n");
+    } catch {
+      // Keep the location even if the file can't be read
+    }
+
+    const lineText = lines[target.line] ?? "";
+    const label =
+      target.label ?? identifierAt(lineText, target.character) ?? lineText.trim();
+
+    const preview: string[] = [];
+    for (
+      let i = Math.max(0, target.line - CONTEXT_LINES);
+      i <= Math.min(lines.length - 1, target.line + CONTEXT_LINES);
+      i++
+    ) {
+      preview.push(`${i + 1}: ${lines[i]}`);
+    }
+
+    messages.push(
+      `\n${path.relative(root, target.filePath)}:${target.line + 1}:${target.character + 1} - ${label}\n${preview.join("\n")}`
+    );
+  }
+
+  return messages.join("\n\n");
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+  const { mkdtempSync, writeFileSync } = await import("fs");
+  const { tmpdir } = await import("os");
+
+  describe("toNavigationTargets", () => {
+    it("should use the selection range of location links", () => {
+      const range = (line: number) => ({
+        start: { line, character: 4 },
+        end: { line, character: 9 },
+      });
+      expect(
+        toNavigationTargets([
+          {
+            targetUri: "file:///work/src/lib.rs",
+            targetRange: range(35),
+            targetSelectionRange: range(36),
+          },
+        ])
+      ).toEqual([{ filePath: "/work/src/lib.rs", line: 36, character: 4 }]);
+      expect(
+        toNavigationTargets({ uri: "file:///work/Cargo.toml", range: range(0) })
+      ).toEqual([{ filePath: "/work/Cargo.toml", line: 0, character: 4 }]);
+      expect(toNavigationTargets(null)).toEqual([]);
+    });
+  });
+
+  describe("formatNavigationTargets", () => {
+    it("should format file:line:column with the identifier and a preview", () => {
+      const root = mkdtempSync(path.join(tmpdir(), "lsmcp-nav-"));
+      writeFileSync(
+        path.join(root, "lib.rs"),
+        ["pub fn greet() {}", "", "#[cfg(test)]", "mod tests {", "}"].join("\n")
+      );
+
+      const output = formatNavigationTargets(root, "Found 1 parent module", [
+        { filePath: path.join(root, "lib.rs"), line: 3, character: 4 },
+      ]);
+
+      expect(output).toBe(
+        [
+          "Found 1 parent module",
+          "",
+          "",
+          "lib.rs:4:5 - tests",
+          "2: ",
+          "3: #[cfg(test)]",
+          "4: mod tests {",
+          "5: }",
+        ].join("\n")
+      );
+    });
+  });
+}
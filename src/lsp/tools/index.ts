@@ -1,6 +1,7 @@
 export * from "./lspFindReferences.ts";
 export * from "./lspGetDefinitions.ts";
 export * from "./lspGetDiagnostics.ts";
+export * from "./lspGetWorkspaceDiagnostics.ts";
 export * from "./lspGetHover.ts";
 export * from "./lspRenameSymbol.ts";
 export * from "./lspGetDocumentSymbols.ts";
@@ -8,4 +9,19 @@ export * from "./lspGetWorkspaceSymbols.ts";
 export * from "./lspGetCompletion.ts";
 export * from "./lspGetSignatureHelp.ts";
 export * from "./lspGetCodeActions.ts";
-export * from "./lspFormatDocument.ts";
\ No newline at end of file
+export * from "./lspApplyCodeAction.ts";
+export * from "./lspFormatDocument.ts";
+export * from "./lspServerStatus.ts";
+export * from "./lspRunTests.ts";
+export * from "./lspGetCallHierarchy.ts";
+export * from "./lspFindImplementations.ts";
+export * from "./lspGetTypeDefinition.ts";
+export * from "./lspGetTypeHierarchy.ts";
+export * from "./lspGetInlayHints.ts";
+export * from "./lspGetEnclosingRange.ts";
+export * from "./lspMoveFile.ts";
+export * from "./lspExtractFunction.ts";
+export * from "./lspExtractVariable.ts";
+export * from "./lspInlineSymbol.ts";
+export * from "./lspDeleteSymbol.ts";
+export * from "./lspOrganizeImports.ts";
\ No newline at end of file
@@ -0,0 +1,387 @@
+import { z } from "zod";
+import { CodeAction, Command, type Range } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import type { LSPClient } from "../lspTypes.ts";
+import {
+  applyWorkspaceEdit,
+  formatWorkspaceEditSummary,
+  type WorkspaceEditResult,
+} from "../applyWorkspaceEdit.ts";
+import {
+  isCommand,
+  requestCodeActions,
+  resolveCodeActionTarget,
+  withCodeActionDocument,
+  type CodeActionTarget,
+} from "./lspGetCodeActions.ts";
+import { prepareFileContext } from "./lspCommon.ts";
+import { selectByIndexOrLabel } from "../../common/selection.ts";
+
+const schemaShape = {
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File path to apply the code action in (relative to root)"),
+  startLine: z
+    .union([z.number(), z.string()])
+    .describe("Start line number (1-based) or string to match"),
+  endLine: z
+    .union([z.number(), z.string()])
+    .describe("End line number (1-based) or string to match")
+    .optional(),
+  includeKinds: z
+    .array(z.string())
+    .describe("Filter for specific code action kinds (e.g., 'quickfix', 'refactor')")
+    .optional(),
+  index: z
+    .number()
+    .describe("Number of the action as listed by lsmcp_get_code_actions (1-based)")
+    .optional(),
+  title: z
+    .string()
+    .describe("Title of the action (exact match, or a unique case-insensitive substring)")
+    .optional(),
+};
+
+const schema = z.object(schemaShape);
+
+/**
+ * Pick a code action by 1-based index or by title
+ */
+export function selectCodeAction(
+  actions: (Command | CodeAction)[],
+  index?: number,
+  title?: string
+): Command | CodeAction {
+  return selectByIndexOrLabel(
+    actions,
+    (action) => action.title,
+    { singular: "code action", plural: "code actions", labelName: "title" },
+    index,
+    title
+  );
+}
+
+export interface CodeActionRun {
+  // Edits of the action itself and those the command pushed through workspace/applyEdit
+  editResults: WorkspaceEditResult[];
+  command?: Command;
+  commandResult?: unknown;
+}
+
+/**
+ * Apply a code action's edit and run its command.
+ * In dry-run mode the edit is only previewed, so actions with a command are refused.
+ */
+export async function runCodeAction(
+  client: LSPClient,
+  selected: Command | CodeAction,
+  root: string,
+  dryRun = false
+): Promise<CodeActionRun> {
+  let command: Command | undefined;
+  let editResult: WorkspaceEditResult | undefined;
+
+  if (isCommand(selected)) {
+    command = selected;
+  } else {
+    if (selected.disabled) {
+      throw new Error(
+        `Code action "${selected.title}" is disabled: ${selected.disabled.reason}`
+      );
+    }
+
+    // Servers may defer computing the edit until codeAction/resolve
+    const action = selected.edit
+      ? selected
+      : await client.resolveCodeAction(selected);
+
+    if (action.edit) {
+      editResult = applyWorkspaceEdit(action.edit, { dryRun, root });
+    }
+    command = action.command;
+  }
+
+  // Commands apply their edits through the server, so they cannot be previewed
+  if (dryRun && command) {
+    throw new Error(
+      `Code action "${selected.title}" runs the command ${command.command} and cannot be previewed with dryRun`
+    );
+  }
+
+  // Commands may push edits back through workspace/applyEdit
+  const editResults = editResult ? [editResult] : [];
+  let commandResult: unknown;
+  if (command) {
+    const onApplyEdit = (event: unknown) => {
+      editResults.push((event as { result: WorkspaceEditResult }).result);
+    };
+    client.on("applyEdit", onApplyEdit);
+    try {
+      commandResult = await client.executeCommand(
+        command.command,
+        command.arguments
+      );
+    } finally {
+      client.off("applyEdit", onApplyEdit);
+    }
+  }
+
+  return { editResults, command, commandResult };
+}
+
+/**
+ * Apply a code action's edit and run its command, returning the summary and diff
+ */
+export async function applyCodeAction(
+  client: LSPClient,
+  selected: Command | CodeAction,
+  root: string,
+  dryRun = false
+): Promise<string> {
+  const { editResults, command, commandResult } = await runCodeAction(
+    client,
+    selected,
+    root,
+    dryRun
+  );
+
+  const lines = [
+    dryRun
+      ? `Dry run: code action ${selected.title}. No files were modified.`
+      : `Applied code action: ${selected.title}`,
+  ];
+  for (const result of editResults) {
+    if (result.files.length > 0) {
+      lines.push("", formatWorkspaceEditSummary(result, root));
+    }
+  }
+  if (command) {
+    lines.push("", `Executed command: ${command.command}`);
+    if (commandResult !== null && commandResult !== undefined) {
+      lines.push(JSON.stringify(commandResult, null, 2));
+    }
+  }
+  const diff = editResults
+    .map((result) => result.diff)
+    .filter(Boolean)
+    .join("\n");
+  if (diff) {
+    lines.push("", diff);
+  } else {
+    lines.push("", "The code action did not produce any changes");
+  }
+
+  return lines.join("\n");
+}
+
+export interface RefactorKind {
+  // Name used in messages, e.g. "extract function"
+  label: string;
+  // Code action kinds of the wanted actions, e.g. "refactor.extract.function"
+  kinds: string[];
+  // Matches the titles of the wanted actions when the server only reports a
+  // parent kind, e.g. rust-analyzer's "refactor.extract"
+  titlePattern: RegExp;
+}
+
+function matchesRefactorKind(action: CodeAction, refactor: RefactorKind): boolean {
+  const kind = action.kind;
+  if (!kind) {
+    return false;
+  }
+  if (refactor.kinds.some((wanted) => kind === wanted || kind.startsWith(`${wanted}.`))) {
+    return true;
+  }
+  return (
+    refactor.kinds.some((wanted) => wanted.startsWith(`${kind}.`)) &&
+    refactor.titlePattern.test(action.title)
+  );
+}
+
+/**
+ * Pick the action for a dedicated refactoring tool: the enabled actions of the
+ * refactoring's kinds (or of a parent kind with a matching title), narrowed by
+ * title when given.
+ * Without a title, the server's preferred action wins, then the first one.
+ */
+export function selectRefactorAction(
+  actions: (Command | CodeAction)[],
+  refactor: RefactorKind,
+  title?: string
+): CodeAction | undefined {
+  const candidates = actions.filter(
+    (action): action is CodeAction =>
+      !isCommand(action) &&
+      !action.disabled &&
+      matchesRefactorKind(action, refactor)
+  );
+  if (candidates.length === 0) {
+    return undefined;
+  }
+  if (title !== undefined) {
+    return selectCodeAction(candidates, undefined, title) as CodeAction;
+  }
+  return candidates.find((action) => action.isPreferred) ?? candidates[0];
+}
+
+/**
+ * Request the code actions for a range, pick the refactoring and apply it.
+ * The range is resolved once the document is open, so it may use semantic tokens.
+ */
+export async function applyRefactor(
+  root: string,
+  filePath: string,
+  refactor: RefactorKind,
+  resolveRange: (fileUri: string, content: string) => Promise<Range>,
+  title?: string,
+  dryRun?: boolean
+): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  const client = getLSPClient();
+  if (!client) {
+    throw new Error("LSP client not initialized");
+  }
+
+  return withCodeActionDocument(client, fileUri, content, async () => {
+    const range = await resolveRange(fileUri, content);
+    const target: CodeActionTarget = {
+      fileUri,
+      content,
+      startLineIndex: range.start.line,
+      endLineIndex: range.end.line,
+      range,
+    };
+
+    const { actions } = await requestCodeActions(client, target);
+    const selected = selectRefactorAction(actions, refactor, title);
+    if (!selected) {
+      const lines = [
+        `No ${refactor.label} refactoring available for ${filePath}:${range.start.line + 1}-${range.end.line + 1}`,
+      ];
+      if (actions.length > 0) {
+        lines.push("", "Available code actions:", ...actions.map((action) => `  - ${action.title}`));
+      }
+      return lines.join("\n");
+    }
+    return applyCodeAction(client, selected, root, dryRun);
+  });
+}
+
+async function handleApplyCodeAction({
+  root,
+  filePath,
+  startLine,
+  endLine,
+  includeKinds,
+  index,
+  title,
+}: z.infer<typeof schema>): Promise<string> {
+  const client = getLSPClient();
+  if (!client) {
+    throw new Error("LSP client not initialized");
+  }
+
+  const target = await resolveCodeActionTarget(root, filePath, startLine, endLine);
+  const { fileUri, content, startLineIndex, endLineIndex } = target;
+
+  return withCodeActionDocument(client, fileUri, content, async () => {
+    const { filteredActions } = await requestCodeActions(
+      client,
+      target,
+      includeKinds
+    );
+    if (filteredActions.length === 0) {
+      return `No code actions available for ${filePath}:${startLineIndex + 1}-${endLineIndex + 1}`;
+    }
+
+    const selected = selectCodeAction(filteredActions, index, title);
+    return applyCodeAction(client, selected, root);
+  });
+}
+
+export const lspApplyCodeActionTool: ToolDef<typeof schema> = {
+  name: "lsmcp_apply_code_action",
+  description:
+    "Apply a code action (quick fix, refactoring, etc.) chosen by index or title from lsmcp_get_code_actions, and show the resulting diff",
+  schema,
+  execute: async (args) => {
+    return handleApplyCodeAction(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("selectCodeAction", () => {
+    const actions: CodeAction[] = [
+      { title: "Add missing import 'foo'", kind: "quickfix" },
+      { title: "Add missing import 'bar'", kind: "quickfix" },
+      { title: "Extract to function", kind: "refactor.extract" },
+    ];
+
+    it("should select by 1-based index", () => {
+      expect(selectCodeAction(actions, 3).title).toBe("Extract to function");
+    });
+
+    it("should select by exact title or unique substring", () => {
+      expect(selectCodeAction(actions, undefined, "Add missing import 'bar'")).toBe(actions[1]);
+      expect(selectCodeAction(actions, undefined, "extract")).toBe(actions[2]);
+    });
+
+    it("should reject ambiguous or unknown selections", () => {
+      expect(() => selectCodeAction(actions, undefined, "missing import")).toThrow(
+        "Multiple code actions"
+      );
+      expect(() => selectCodeAction(actions, 4)).toThrow("No code action with index 4");
+      expect(() => selectCodeAction(actions)).toThrow("Either index or title");
+    });
+  });
+
+  describe("selectRefactorAction", () => {
+    const extractFunction: RefactorKind = {
+      label: "extract function",
+      kinds: ["refactor.extract.function"],
+      titlePattern: /^Extract into function\b/i,
+    };
+    const actions: CodeAction[] = [
+      { title: "Extract to constant in enclosing scope", kind: "refactor.extract.constant" },
+      { title: "Extract to constant in function 'main'", kind: "refactor.extract.constant" },
+      { title: "Extract to inner function in function 'main'", kind: "refactor.extract.function" },
+      { title: "Extract to function in module scope", kind: "refactor.extract.function", isPreferred: true },
+      { title: "Move to a new file", kind: "refactor.move.newFile" },
+    ];
+
+    it("should prefer the preferred action among matching kinds", () => {
+      expect(selectRefactorAction(actions, extractFunction)).toBe(actions[3]);
+    });
+
+    it("should narrow the candidates by title", () => {
+      expect(selectRefactorAction(actions, extractFunction, "inner")).toBe(actions[2]);
+    });
+
+    it("should not match other sub-kinds whose title mentions a function", () => {
+      expect(selectRefactorAction(actions.slice(0, 2), extractFunction)).toBeUndefined();
+    });
+
+    it("should match titles when the server only reports the parent kind", () => {
+      // rust-analyzer reports every extract assist as "refactor.extract"
+      const rustActions: CodeAction[] = [
+        { title: "Extract into variable", kind: "refactor.extract" },
+        { title: "Extract into function", kind: "refactor.extract" },
+      ];
+      expect(selectRefactorAction(rustActions, extractFunction)).toBe(rustActions[1]);
+    });
+
+    it("should ignore disabled actions and other kinds", () => {
+      const disabled: CodeAction = {
+        title: "Extract into function",
+        kind: "refactor.extract",
+        disabled: { reason: "Cannot extract an empty selection" },
+      };
+      expect(selectRefactorAction([disabled, actions[4]], extractFunction)).toBeUndefined();
+    });
+  });
+}
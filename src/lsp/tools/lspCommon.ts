@@ -4,7 +4,10 @@ import fs from "fs/promises";
 import { pathToFileURL } from "url";
 import { getLSPClient } from "../lspClient.ts";
 import { resolveLineParameter } from "../../textUtils/resolveLineParameter.ts";
+import { findSymbolInLine } from "../../textUtils/findSymbolInLine.ts";
+import type { SemanticToken } from "../../textUtils/semanticTokens.ts";
 import { formatError, ErrorContext } from "../../mcp/utils/errorHandler.ts";
+import { debug } from "../../mcp/_mcplib.ts";
 
 // Common schema shapes for LSP tools
 export const filePathShape = {
@@ -28,6 +31,21 @@ export const characterShape = {
   character: z.number().describe("Character position in the line (0-based)"),
 };
 
+export const waitForIdleShape = {
+  waitForIdle: z
+    .boolean()
+    .optional()
+    .describe("Wait until the language server finishes indexing (e.g. rust-analyzer startup) before querying"),
+};
+
+// Wait for in-flight server work (indexing, cargo metadata, ...) when requested
+export async function waitForIdleIfRequested(waitForIdle?: boolean): Promise<void> {
+  const client = getLSPClient();
+  if (waitForIdle && client && !(await client.waitForIdle())) {
+    debug("[lsp] Server is still busy, querying anyway");
+  }
+}
+
 // Common file operations
 export async function prepareFileContext(
   root: string,
@@ -118,4 +136,48 @@ export function resolveLineOrThrow(
   }
   
   return resolveResult.lineIndex;
+}
+
+// Resolve a line and optional target text to an LSP position.
+// Without a target, the position is the first non-whitespace character of the line.
+// Semantic tokens, when given, restrict matches to symbols (not strings or comments).
+export function resolvePositionOrThrow(
+  content: string,
+  line: string | number,
+  target: string | undefined,
+  filePath: string,
+  tokens?: SemanticToken[]
+): { line: number; character: number } {
+  const lineIndex = resolveLineOrThrow(content, line, filePath);
+  const lineText = content.split("\n")[lineIndex] ?? "";
+
+  if (!target) {
+    return { line: lineIndex, character: lineText.length - lineText.trimStart().length };
+  }
+
+  const lineTokens = tokens?.filter((token) => token.line === lineIndex);
+  const symbolResult = findSymbolInLine(lineText, target, 0, lineTokens);
+  if ("characterIndex" in symbolResult) {
+    return { line: lineIndex, character: symbolResult.characterIndex };
+  }
+  // Fall back to a plain text match for targets such as "println!"
+  const index = lineText.indexOf(target);
+  if (index === -1) {
+    throw new Error(`${symbolResult.error} on line ${lineIndex + 1} of ${filePath}`);
+  }
+  return { line: lineIndex, character: index };
+}
+
+// Like resolvePositionOrThrow, using the server's semantic tokens to classify
+// matches of the target. The document must already be open.
+export async function resolveTargetPosition(
+  fileUri: string,
+  content: string,
+  line: string | number,
+  target: string | undefined,
+  filePath: string
+): Promise<{ line: number; character: number }> {
+  const client = getLSPClient();
+  const tokens = target && client ? await client.getSemanticTokens(fileUri) : null;
+  return resolvePositionOrThrow(content, line, target, filePath, tokens ?? undefined);
 }
\ No newline at end of file
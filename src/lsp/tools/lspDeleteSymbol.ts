@@ -1,13 +1,26 @@
 import { z } from "zod";
 import path from "path";
-import fs from "fs/promises";
-import { fileURLToPath, pathToFileURL } from "url";
-import { WorkspaceEdit, TextEdit, Range, Position, Location } from "vscode-languageserver-types";
+import { fileURLToPath } from "url";
+import type {
+  DocumentSymbol,
+  Location,
+  Position,
+  Range,
+  SymbolInformation,
+  SymbolKind,
+} from "vscode-languageserver-types";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
 import { getLSPClient } from "../lspClient.ts";
-import { resolveLineParameter } from "../../textUtils/resolveLineParameter.ts";
+import { applyWorkspaceEdit } from "../applyWorkspaceEdit.ts";
+import { formatNavigationTargets, toNavigationTargets } from "../navigationTargets.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  withLSPDocument,
+} from "./lspCommon.ts";
+import { getSymbolKindName } from "./lspGetDocumentSymbols.ts";
 
-const schemaShape = {
+const schema = z.object({
   root: z.string().describe("Root directory for resolving relative paths"),
   filePath: z
     .string()
@@ -16,210 +29,282 @@ const schemaShape = {
     .union([z.number(), z.string()])
     .describe("Line number (1-based) or string to match in the line"),
   target: z.string().describe("Name of the symbol to delete"),
-  removeReferences: z
+  dryRun: z
     .boolean()
     .optional()
-    .default(true)
-    .describe("Also delete all references to the symbol"),
-};
+    .describe("Preview the deletion as a unified diff without modifying any files"),
+});
 
-const schema = z.object(schemaShape);
+type DeleteSymbolRequest = z.infer<typeof schema>;
 
-interface DeleteSymbolResult {
-  applied: boolean;
-  deletedFromFiles: Set<string>;
-  totalDeleted: number;
-  failureReason?: string;
+export interface SymbolDeclaration {
+  name: string;
+  kind: SymbolKind;
+  // Full extent of the declaration
+  range: Range;
 }
 
-async function handleDeleteSymbol({
-  root,
-  filePath,
-  line,
-  target,
-  removeReferences = true,
-}: z.infer<typeof schema>): Promise<DeleteSymbolResult> {
-  const client = getLSPClient();
-  if (!client) {
-    throw new Error("LSP client not initialized");
-  }
+function comparePositions(a: Position, b: Position): number {
+  return a.line !== b.line ? a.line - b.line : a.character - b.character;
+}
 
-  // Convert to absolute path
-  const absolutePath = path.isAbsolute(filePath)
-    ? filePath
-    : path.join(root, filePath);
-
-  // Convert to file URI
-  const fileUri = pathToFileURL(absolutePath).toString();
-
-  // Read the file content
-  const content = await fs.readFile(absolutePath, "utf-8");
-  const lines = content.split("\n");
-
-  // Open the document in LSP
-  client.openDocument(fileUri, content);
-
-  let locations: Location[] = [];
-  
-  try {
-    // Resolve line parameter
-    const resolveResult = resolveLineParameter(content, line);
-    
-    if (!resolveResult.success) {
-      throw new Error(resolveResult.error);
-    }
-    
-    const resolvedLine = resolveResult.lineIndex + 1; // Convert back to 1-based
+function rangeContains(outer: Range, inner: Range): boolean {
+  return (
+    comparePositions(outer.start, inner.start) <= 0 &&
+    comparePositions(inner.end, outer.end) <= 0
+  );
+}
 
-    // Find the symbol position on the line
-    const lineContent = lines[resolveResult.lineIndex];
-    const symbolIndex = lineContent.indexOf(target);
+/**
+ * Find the innermost declaration at a position: a document symbol whose name
+ * range contains the position, or a flat symbol named `name` whose range does
+ */
+export function findDeclarationAt(
+  symbols: DocumentSymbol[] | SymbolInformation[],
+  position: Position,
+  name: string
+): SymbolDeclaration | undefined {
+  const at: Range = { start: position, end: position };
+  const candidates: SymbolDeclaration[] = [];
 
-    if (symbolIndex === -1) {
-      throw new Error(
-        `Symbol "${target}" not found on line ${resolvedLine}: "${lineContent.trim()}"`
-      );
+  const visit = (symbol: DocumentSymbol) => {
+    if (rangeContains(symbol.selectionRange, at)) {
+      candidates.push({ name: symbol.name, kind: symbol.kind, range: symbol.range });
     }
+    symbol.children?.forEach(visit);
+  };
 
-    // Create position for the symbol
-    const position: Position = {
-      line: resolveResult.lineIndex, // LSP uses 0-based line numbers
-      character: symbolIndex,
-    };
+  for (const symbol of symbols) {
+    if ("selectionRange" in symbol) {
+      visit(symbol);
+    } else if (symbol.name === name && rangeContains(symbol.location.range, at)) {
+      candidates.push({ name: symbol.name, kind: symbol.kind, range: symbol.location.range });
+    }
+  }
+
+  // Candidates are nested, so the innermost is contained in all others
+  return candidates.reduce<SymbolDeclaration | undefined>(
+    (inner, candidate) =>
+      !inner || rangeContains(inner.range, candidate.range) ? candidate : inner,
+    undefined
+  );
+}
 
-    // First, find all references if removeReferences is true
-    locations = removeReferences
-      ? await client.findReferences(fileUri, position)
-      : [{ uri: fileUri, range: { start: position, end: position } }];
+// Outer doc comments, attributes (#[derive(...)]) and decorators attached above an item
+const ATTACHED_LINE = /^(\/\/\/|#\[|@[A-Za-z_])/;
+// Keywords that may precede a declaration's range, e.g. `export const` for a TypeScript variable
+const DECLARATION_PREFIX = /^\s*((export|default|declare|const|let|var|pub(\([^)]*\))?)\s+)*$/;
 
-    if (locations.length === 0) {
+/**
+ * Extend a declaration range to the text to delete: the attached doc comments,
+ * attributes and decorators above it, whole lines when nothing else shares them,
+ * and a following blank line when the declaration sits between blank lines.
+ * A declaration sharing its line with others in a list (`const a = 1, b = 2;`)
+ * is removed with its comma. Returns undefined when other code shares the line.
+ */
+export function findDeletionRange(lines: string[], range: Range): Range | undefined {
+  const before = lines[range.start.line].slice(0, range.start.character);
+  const after = lines[range.end.line].slice(range.end.character);
+  const ownsLines =
+    (DECLARATION_PREFIX.test(before) && /^\s*;?\s*$/.test(after)) ||
+    (before.trim() === "" && /^\s*,\s*$/.test(after));
+
+  if (!ownsLines) {
+    const following = /^\s*,[ \t]*/.exec(after);
+    if (following) {
       return {
-        applied: false,
-        deletedFromFiles: new Set(),
-        totalDeleted: 0,
-        failureReason: "No references found for the symbol",
+        start: range.start,
+        end: { line: range.end.line, character: range.end.character + following[0].length },
       };
     }
-
-    // Create workspace edit
-    const workspaceEdit: WorkspaceEdit = {
-      changes: {},
-    };
-
-    // Group locations by file
-    const fileChanges = new Map<string, Range[]>();
-    
-    for (const location of locations) {
-      const ranges = fileChanges.get(location.uri) || [];
-      ranges.push(location.range);
-      fileChanges.set(location.uri, ranges);
+    const preceding = /,\s*$/.exec(before);
+    if (preceding) {
+      return {
+        start: { line: range.start.line, character: range.start.character - preceding[0].length },
+        end: range.end,
+      };
     }
+    return undefined;
+  }
 
-    // Create text edits for each file
-    for (const [uri, ranges] of fileChanges) {
-      // Sort ranges in reverse order to avoid position shifts
-      const sortedRanges = ranges.sort((a, b) => {
-        if (a.start.line !== b.start.line) {
-          return b.start.line - a.start.line;
-        }
-        return b.start.character - a.start.character;
-      });
-
-      // Read file content if it's different from the current file
-      let fileContent: string;
-      let fileLines: string[];
-      
-      if (uri === fileUri) {
-        fileContent = content;
-        fileLines = lines;
-      } else {
-        const filePath = fileURLToPath(uri);
-        fileContent = await fs.readFile(filePath, "utf-8");
-        fileLines = fileContent.split("\n");
-        // Open document for editing
-        client.openDocument(uri, fileContent);
+  let startLine = range.start.line;
+  while (startLine > 0) {
+    const previous = lines[startLine - 1].trim();
+    if (ATTACHED_LINE.test(previous)) {
+      startLine--;
+      continue;
+    }
+    // A /** ... */ block comment ending right above
+    if (previous.endsWith("*/")) {
+      let blockStart = startLine - 1;
+      while (blockStart >= 0 && !lines[blockStart].trim().startsWith("/*")) {
+        blockStart--;
       }
-
-      const edits: TextEdit[] = [];
-
-      for (const range of sortedRanges) {
-        // Check if this is a complete line deletion
-        const lineText = fileLines[range.start.line];
-        const beforeSymbol = lineText.substring(0, range.start.character).trim();
-        const afterSymbol = lineText.substring(range.end.character).trim();
-
-        if (!beforeSymbol && !afterSymbol) {
-          // Delete the entire line
-          edits.push({
-            range: {
-              start: { line: range.start.line, character: 0 },
-              end: { line: range.start.line + 1, character: 0 },
-            },
-            newText: "",
-          });
-        } else {
-          // Delete just the symbol
-          edits.push({
-            range,
-            newText: "",
-          });
-        }
+      if (blockStart >= 0 && lines[blockStart].trim().startsWith("/**")) {
+        startLine = blockStart;
+        continue;
       }
-
-      workspaceEdit.changes![uri] = edits;
     }
+    break;
+  }
 
-    // Apply the workspace edit
-    const result = await client.applyEdit(workspaceEdit, `Delete symbol "${target}"`);
+  let endLine = range.end.line + 1;
+  const blankAbove = startLine === 0 || lines[startLine - 1].trim() === "";
+  if (blankAbove && endLine < lines.length && lines[endLine].trim() === "") {
+    endLine++;
+  }
 
-    if (!result.applied) {
-      return {
-        applied: false,
-        deletedFromFiles: new Set(),
-        totalDeleted: 0,
-        failureReason: result.failureReason || "Failed to apply workspace edit",
-      };
-    }
+  return {
+    start: { line: startLine, character: 0 },
+    end: { line: endLine, character: 0 },
+  };
+}
+
+async function handleDeleteSymbol({
+  root,
+  filePath,
+  line,
+  target,
+  dryRun,
+}: DeleteSymbolRequest): Promise<string> {
+  const { absolutePath, fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    const client = getLSPClient()!;
 
-    return {
-      applied: true,
-      deletedFromFiles: new Set(fileChanges.keys()),
-      totalDeleted: locations.length,
-    };
-  } finally {
-    // Close all opened documents
-    for (const uri of new Set([fileUri, ...locations.map(l => l.uri)])) {
-      client.closeDocument(uri);
+    const symbols = await client.getDocumentSymbols(fileUri);
+    const declaration = findDeclarationAt(symbols, position, target);
+    if (!declaration) {
+      throw new Error(
+        `No declaration of "${target}" found at ${filePath}:${position.line + 1}:${position.character + 1}`
+      );
     }
-  }
-}
 
-function formatDeleteSymbolResult(result: DeleteSymbolResult): string {
-  if (!result.applied) {
-    return `Failed to delete symbol: ${result.failureReason}`;
-  }
+    // Uses inside the declaration itself (e.g. recursion) go away with it
+    const references = await client.findReferences(fileUri, position);
+    const external = references.filter(
+      (reference: Location) =>
+        fileURLToPath(reference.uri) !== path.resolve(absolutePath) ||
+        !rangeContains(declaration.range, reference.range)
+    );
+    const kind = getSymbolKindName(declaration.kind);
+    if (external.length > 0) {
+      return formatNavigationTargets(
+        root,
+        `Not deleted: ${kind} "${declaration.name}" is still used in ${external.length} place(s). Remove these uses first:`,
+        toNavigationTargets(external)
+      );
+    }
 
-  const fileCount = result.deletedFromFiles.size;
-  const fileList = Array.from(result.deletedFromFiles)
-    .map(uri => {
-      try {
-        return fileURLToPath(uri);
-      } catch {
-        return uri;
-      }
-    })
-    .join("\n  ");
+    const range = findDeletionRange(content.split("\n"), declaration.range);
+    if (!range) {
+      return `Not deleted: ${kind} "${declaration.name}" shares ${filePath}:${declaration.range.start.line + 1} with other code. Edit the line by hand.`;
+    }
+    const result = applyWorkspaceEdit(
+      { changes: { [fileUri]: [{ range, newText: "" }] } },
+      { dryRun, root }
+    );
 
-  return `Successfully deleted symbol from ${fileCount} file(s) with ${result.totalDeleted} occurrence(s)\n\nModified files:\n  ${fileList}`;
+    const lines = [
+      dryRun
+        ? `Dry run: would delete ${kind} "${declaration.name}" from ${filePath}. No files were modified.`
+        : `Deleted ${kind} "${declaration.name}" from ${filePath} (no references remained)`,
+    ];
+    if (result.diff) {
+      lines.push("", result.diff);
+    }
+    return lines.join("\n");
+  });
 }
 
 export const lspDeleteSymbolTool: ToolDef<typeof schema> = {
-  name: "lsmcp_delete_symbol",
+  name: "lsmcp_delete_unused_symbol",
   description:
-    "Delete a symbol (variable, function, class, etc.) and optionally all its references using LSP",
+    "Delete an unused declaration (function, type, variable, ...) with its doc comments and attributes using LSP, refusing and listing the uses if it is still referenced",
   schema,
   execute: async (args) => {
-    const result = await handleDeleteSymbol(args);
-    return formatDeleteSymbolResult(result);
+    return handleDeleteSymbol(args);
   },
-};
\ No newline at end of file
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  const range = (startLine: number, startChar: number, endLine: number, endChar: number): Range => ({
+    start: { line: startLine, character: startChar },
+    end: { line: endLine, character: endChar },
+  });
+
+  describe("findDeclarationAt", () => {
+    const symbols: DocumentSymbol[] = [
+      {
+        name: "Calculator",
+        kind: 5, // Class
+        range: range(0, 0, 10, 1),
+        selectionRange: range(0, 6, 0, 16),
+        children: [
+          {
+            name: "add",
+            kind: 6, // Method
+            range: range(2, 2, 4, 3),
+            selectionRange: range(2, 2, 2, 5),
+          },
+        ],
+      },
+    ];
+
+    it("should find the innermost symbol whose name is at the position", () => {
+      expect(findDeclarationAt(symbols, { line: 2, character: 3 }, "add")).toEqual({
+        name: "add",
+        kind: 6,
+        range: range(2, 2, 4, 3),
+      });
+    });
+
+    it("should not match positions inside a body", () => {
+      expect(findDeclarationAt(symbols, { line: 3, character: 4 }, "value")).toBeUndefined();
+    });
+  });
+
+  describe("findDeletionRange", () => {
+    it("should include doc comments and attributes of a Rust item", () => {
+      const lines = [
+        "use std::fmt;",
+        "",
+        "/// A unit of work",
+        "#[derive(Debug, Clone)]",
+        "pub struct Task {",
+        "    id: u32,",
+        "}",
+        "",
+        "fn main() {}",
+      ];
+      expect(findDeletionRange(lines, range(4, 0, 6, 1))).toEqual(range(2, 0, 8, 0));
+    });
+
+    it("should include a JSDoc block and the declaration keywords of a variable", () => {
+      const lines = [
+        "/**",
+        " * Default timeout",
+        " */",
+        "export const TIMEOUT = 1000;",
+        "const other = 2;",
+      ];
+      expect(findDeletionRange(lines, range(3, 13, 3, 27))).toEqual(range(0, 0, 4, 0));
+    });
+
+    it("should remove the comma of a declarator sharing its line", () => {
+      const lines = ["const a = 1, b = 2;"];
+      // "b = 2" -> "const a = 1;"
+      expect(findDeletionRange(lines, range(0, 13, 0, 18))).toEqual(range(0, 11, 0, 18));
+      // "a = 1" -> "const b = 2;"
+      expect(findDeletionRange(lines, range(0, 6, 0, 11))).toEqual(range(0, 6, 0, 13));
+    });
+
+    it("should refuse when other statements share the line", () => {
+      const lines = ["let x = 1; let y = 2;"];
+      expect(findDeletionRange(lines, range(0, 4, 0, 9))).toBeUndefined();
+    });
+  });
+}
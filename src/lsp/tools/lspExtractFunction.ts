@@ -0,0 +1,61 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { applyRefactor, type RefactorKind } from "./lspApplyCodeAction.ts";
+import { resolveLineOrThrow } from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File path (relative to root)"),
+  startLine: z
+    .union([z.number(), z.string()])
+    .describe("First line to extract: line number (1-based) or string to match"),
+  endLine: z
+    .union([z.number(), z.string()])
+    .describe("Last line to extract: line number (1-based) or string to match (defaults to startLine)")
+    .optional(),
+  title: z
+    .string()
+    .describe("Title (or unique substring) of the action when the server offers several, e.g. 'module scope'")
+    .optional(),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the extraction as a unified diff without modifying any files"),
+});
+
+const EXTRACT_FUNCTION: RefactorKind = {
+  label: "extract function",
+  // TypeScript: "Extract to function in module scope" (refactor.extract.function)
+  kinds: ["refactor.extract.function"],
+  // rust-analyzer reports "Extract into function" as refactor.extract
+  titlePattern: /^Extract into function\b/i,
+};
+
+export const lspExtractFunctionTool: ToolDef<typeof schema> = {
+  name: "lsmcp_extract_function",
+  description:
+    "Extract a range of lines into a new function using the language server's extract refactoring, and show the diff",
+  schema,
+  execute: async ({ root, filePath, startLine, endLine, title, dryRun }) => {
+    return applyRefactor(
+      root,
+      filePath,
+      EXTRACT_FUNCTION,
+      async (_fileUri, content) => {
+        const start = resolveLineOrThrow(content, startLine, filePath);
+        const end = endLine !== undefined ? resolveLineOrThrow(content, endLine, filePath) : start;
+        if (end < start) {
+          throw new Error(`endLine (${end + 1}) is before startLine (${start + 1})`);
+        }
+        // Select the statements without the surrounding indentation
+        const lines = content.split("\n");
+        return {
+          start: { line: start, character: lines[start].length - lines[start].trimStart().length },
+          end: { line: end, character: lines[end].trimEnd().length },
+        };
+      },
+      title,
+      dryRun
+    );
+  },
+};
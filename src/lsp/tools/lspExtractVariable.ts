@@ -0,0 +1,54 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { applyRefactor, type RefactorKind } from "./lspApplyCodeAction.ts";
+import { resolvePositionOrThrow } from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File path (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  expression: z
+    .string()
+    .describe("Expression on the line to extract, exactly as written (e.g. 'a * 2 + b')"),
+  title: z
+    .string()
+    .describe("Title (or unique substring) of the action when the server offers several, e.g. 'constant'")
+    .optional(),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the extraction as a unified diff without modifying any files"),
+});
+
+const EXTRACT_VARIABLE: RefactorKind = {
+  label: "extract variable",
+  // TypeScript: "Extract to constant in enclosing scope" (refactor.extract.constant)
+  kinds: ["refactor.extract.constant", "refactor.extract.variable"],
+  // rust-analyzer reports "Extract into variable" as refactor.extract
+  titlePattern: /^Extract into variable\b/i,
+};
+
+export const lspExtractVariableTool: ToolDef<typeof schema> = {
+  name: "lsmcp_extract_variable",
+  description:
+    "Extract an expression into a new local variable using the language server's extract refactoring, and show the diff",
+  schema,
+  execute: async ({ root, filePath, line, expression, title, dryRun }) => {
+    return applyRefactor(
+      root,
+      filePath,
+      EXTRACT_VARIABLE,
+      async (_fileUri, content) => {
+        const start = resolvePositionOrThrow(content, line, expression, filePath);
+        return {
+          start,
+          end: { line: start.line, character: start.character + expression.length },
+        };
+      },
+      title,
+      dryRun
+    );
+  },
+};
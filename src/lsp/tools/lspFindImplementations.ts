@@ -0,0 +1,60 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import { formatNavigationTargets, toNavigationTargets } from "../navigationTargets.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File path containing the symbol (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  symbolName: z
+    .string()
+    .describe("Name of the trait, interface or method to find implementations of"),
+  ...waitForIdleShape,
+});
+
+async function handleFindImplementations({
+  root,
+  filePath,
+  line,
+  symbolName,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, symbolName, filePath);
+    await waitForIdleIfRequested(waitForIdle);
+    const client = getLSPClient()!;
+
+    const targets = toNavigationTargets(
+      await client.getImplementation(fileUri, position)
+    );
+    return formatNavigationTargets(
+      root,
+      `Found ${targets.length} implementation${targets.length === 1 ? "" : "s"} of "${symbolName}"`,
+      targets
+    );
+  });
+}
+
+export const lspFindImplementationsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_find_implementations",
+  description:
+    "Find the implementations of a trait, interface or abstract method using LSP",
+  schema,
+  execute: async (args) => {
+    return handleFindImplementations(args);
+  },
+};
@@ -6,6 +6,7 @@ import { getActiveClient } from "../lspClient.ts";
 import { parseLineNumber } from "../../textUtils/parseLineNumber.ts";
 import { findSymbolInLine } from "../../textUtils/findSymbolInLine.ts";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { waitForIdleShape, waitForIdleIfRequested } from "./lspCommon.ts";
 import { formatError, ErrorContext } from "../../mcp/utils/errorHandler.ts";
 
 const schema = z.object({
@@ -17,6 +18,7 @@ const schema = z.object({
     .union([z.number(), z.string()])
     .describe("Line number (1-based) or string to match in the line"),
   symbolName: z.string().describe("Name of the symbol to find references for"),
+  ...waitForIdleShape,
 });
 
 type FindReferencesRequest = z.infer<typeof schema>;
@@ -162,6 +164,7 @@ export const lspFindReferencesTool: ToolDef<typeof schema> = {
   description: "Find all references to symbol across the codebase using LSP",
   schema,
   execute: async (args: z.infer<typeof schema>) => {
+    await waitForIdleIfRequested(args.waitForIdle);
     const result = await findReferencesWithLSP(args);
     if (result.isOk()) {
       const messages = [result.value.message];
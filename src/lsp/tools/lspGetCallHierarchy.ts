@@ -0,0 +1,160 @@
+import { z } from "zod";
+import path from "path";
+import { fileURLToPath } from "url";
+import type { CallHierarchyItem, Range } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+import {
+  depthSchema,
+  directionSchema,
+  formatHierarchies,
+  type HierarchyNode,
+} from "../hierarchyTree.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File path containing the symbol (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  target: z.string().describe("Name of the function or method on the line"),
+  direction: directionSchema(
+    ["incoming", "outgoing"],
+    "Show callers (incoming), callees (outgoing) or both"
+  ),
+  depth: depthSchema("calls"),
+  ...waitForIdleShape,
+});
+
+type GetCallHierarchyRequest = z.infer<typeof schema>;
+
+// Call sites: in the caller's file for incoming calls, in the parent's file for outgoing calls
+interface CallSites {
+  callSites: { uri: string; range: Range }[];
+}
+
+type CallNode = HierarchyNode<CallHierarchyItem, CallSites>;
+
+function formatCallSites(root: string, node: CallNode): string[] {
+  const sites = node.callSites
+    .map(({ uri, range }) => `${path.relative(root, fileURLToPath(uri))}:${range.start.line + 1}`)
+    .join(", ");
+  return sites ? [`via ${sites}`] : [];
+}
+
+async function handleGetCallHierarchy({
+  root,
+  filePath,
+  line,
+  target,
+  direction,
+  depth,
+  waitForIdle,
+}: GetCallHierarchyRequest): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    await waitForIdleIfRequested(waitForIdle);
+    const client = getLSPClient()!;
+
+    const items = await client.prepareCallHierarchy(fileUri, position);
+    if (items.length === 0) {
+      return `No call hierarchy item found for "${target}" at ${filePath}:${position.line + 1}:${position.character + 1}`;
+    }
+
+    return formatHierarchies<CallHierarchyItem, CallSites>(
+      root,
+      "Call hierarchy",
+      items,
+      [
+        {
+          name: "incoming",
+          heading: "Incoming calls (callers):",
+          getRelated: async (item) =>
+            (await client.getIncomingCalls(item)).map((call) => ({
+              item: call.from,
+              callSites: call.fromRanges.map((range) => ({ uri: call.from.uri, range })),
+            })),
+        },
+        {
+          name: "outgoing",
+          heading: "Outgoing calls (callees):",
+          getRelated: async (item) =>
+            (await client.getOutgoingCalls(item)).map((call) => ({
+              item: call.to,
+              callSites: call.fromRanges.map((range) => ({ uri: item.uri, range })),
+            })),
+        },
+      ],
+      direction,
+      depth,
+      (node) => formatCallSites(root, node)
+    );
+  });
+}
+
+export const lspGetCallHierarchyTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_call_hierarchy",
+  description:
+    "Show the callers and/or callees of a function as a depth-limited tree using LSP call hierarchy",
+  schema,
+  execute: async (args) => {
+    return handleGetCallHierarchy(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+  const { formatHierarchyTree } = await import("../hierarchyTree.ts");
+
+  describe("formatCallSites", () => {
+    const range = (line: number) => ({
+      start: { line, character: 0 },
+      end: { line, character: 5 },
+    });
+    const item = (name: string, uri: string, line: number): CallHierarchyItem => ({
+      name,
+      kind: 12, // Function
+      uri,
+      range: range(line),
+      selectionRange: range(line),
+    });
+
+    it("should list call sites below each node of the tree", () => {
+      const greet = item("greet", "file:///work/src/lib.rs", 30);
+      const main = item("main", "file:///work/src/main.rs", 2);
+      const nodes: CallNode[] = [
+        {
+          item: main,
+          callSites: [{ uri: main.uri, range: range(12) }],
+          children: [
+            {
+              item: greet,
+              callSites: [{ uri: greet.uri, range: range(40) }],
+              children: [],
+              recursive: true,
+            },
+          ],
+        },
+      ];
+      const lines = formatHierarchyTree("/work", nodes, (node) => formatCallSites("/work", node));
+
+      expect(lines).toEqual([
+        "  main [Function] - src/main.rs:3",
+        "    via src/main.rs:13",
+        "    greet [Function] - src/lib.rs:31 (recursive)",
+        "      via src/lib.rs:41",
+      ]);
+    });
+  });
+}
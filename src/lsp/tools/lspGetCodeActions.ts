@@ -2,9 +2,10 @@ import { z } from "zod";
 import path from "path";
 import fs from "fs/promises";
 import { pathToFileURL } from "url";
-import { CodeAction, Command, CodeActionKind } from "vscode-languageserver-types";
+import { CodeAction, Command, CodeActionKind, type Range } from "vscode-languageserver-types";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
 import { getLSPClient } from "../lspClient.ts";
+import type { LSPClient } from "../lspTypes.ts";
 import { resolveLineParameter } from "../../textUtils/resolveLineParameter.ts";
 
 const schemaShape = {
@@ -44,7 +45,7 @@ function getCodeActionKindName(kind?: string | CodeActionKind): string {
   return kind;
 }
 
-function isCommand(action: Command | CodeAction): action is Command {
+export function isCommand(action: Command | CodeAction): action is Command {
   return "command" in action && typeof action.command === "string";
 }
 
@@ -82,6 +83,8 @@ function formatCodeAction(action: Command | CodeAction): string {
       if (changes) {
         const fileCount = Object.keys(changes).length;
         result += `\n  Edits ${fileCount} file(s)`;
+      } else if (action.edit.documentChanges) {
+        result += `\n  Edits ${action.edit.documentChanges.length} document(s)`;
       }
     }
     
@@ -89,18 +92,24 @@ function formatCodeAction(action: Command | CodeAction): string {
   }
 }
 
-async function handleGetCodeActions({
-  root,
-  filePath,
-  startLine,
-  endLine,
-  includeKinds,
-}: z.infer<typeof schema>): Promise<string> {
-  const client = getLSPClient();
-  if (!client) {
-    throw new Error("LSP client not initialized");
-  }
+export interface CodeActionTarget {
+  fileUri: string;
+  content: string;
+  startLineIndex: number;
+  endLineIndex: number;
+  // Exact selection within the lines (defaults to the full lines)
+  range?: Range;
+}
 
+/**
+ * Resolve the file and line range that code actions are requested for
+ */
+export async function resolveCodeActionTarget(
+  root: string,
+  filePath: string,
+  startLine: number | string,
+  endLine?: number | string
+): Promise<CodeActionTarget> {
   // Convert to absolute path
   const absolutePath = path.isAbsolute(filePath)
     ? filePath
@@ -133,49 +142,109 @@ async function handleGetCodeActions({
     endLineIndex = endResolve.lineIndex;
   }
 
-  // Open the document in LSP
+  return { fileUri, content, startLineIndex, endLineIndex };
+}
+
+/**
+ * Open a document for code action requests and run the operation once its
+ * diagnostics are in, since quick fixes are computed from the diagnostics sent
+ * with the request. Prefers pull diagnostics; otherwise listens for
+ * publishDiagnostics before opening so the first publish is not missed.
+ */
+export async function withCodeActionDocument<T>(
+  client: LSPClient,
+  fileUri: string,
+  content: string,
+  operation: () => Promise<T>
+): Promise<T> {
+  const published = client.getServerCapabilities()?.diagnosticProvider
+    ? null
+    : client.waitForDiagnostics(fileUri);
   client.openDocument(fileUri, content);
 
   try {
-    // Wait a bit for LSP to process the document
-    await new Promise(resolve => setTimeout(resolve, 500));
-
-    // Get diagnostics for the range (to provide context for code actions)
-    const diagnostics = client.getDiagnostics(fileUri);
-    const rangeDiagnostics = diagnostics.filter(d => {
-      const line = d.range.start.line;
-      return line >= startLineIndex && line <= endLineIndex;
-    });
+    await (published ?? client.pullDiagnostics(fileUri));
+    return await operation();
+  } finally {
+    client.closeDocument(fileUri);
+  }
+}
 
-    // Get code actions
-    const range = {
-      start: { line: startLineIndex, character: 0 },
-      end: { 
-        line: endLineIndex, 
-        character: content.split('\n')[endLineIndex]?.length ?? 0 
-      },
-    };
-    
-    const actions = await client.getCodeActions(fileUri, range, {
-      diagnostics: rangeDiagnostics,
+/**
+ * Request code actions for a document opened with withCodeActionDocument, filtered by kind.
+ * The order of the filtered list defines the 1-based action numbers.
+ */
+export async function requestCodeActions(
+  client: LSPClient,
+  target: CodeActionTarget,
+  includeKinds?: string[]
+): Promise<{
+  actions: (Command | CodeAction)[];
+  filteredActions: (Command | CodeAction)[];
+}> {
+  const { fileUri, content, startLineIndex, endLineIndex } = target;
+
+  // Get diagnostics for the range (to provide context for code actions)
+  const diagnostics = client.getDiagnostics(fileUri);
+  const rangeDiagnostics = diagnostics.filter(d => {
+    const line = d.range.start.line;
+    return line >= startLineIndex && line <= endLineIndex;
+  });
+
+  // Get code actions
+  const range = target.range ?? {
+    start: { line: startLineIndex, character: 0 },
+    end: { 
+      line: endLineIndex, 
+      character: content.split('\n')[endLineIndex]?.length ?? 0 
+    },
+  };
+  
+  const actions = await client.getCodeActions(fileUri, range, {
+    diagnostics: rangeDiagnostics,
+  });
+
+  // Filter by kinds if specified
+  let filteredActions = actions;
+  if (includeKinds && includeKinds.length > 0) {
+    filteredActions = actions.filter(action => {
+      if (isCommand(action)) {
+        // Commands don't have kinds, so exclude them when filtering
+        return false;
+      }
+      return action.kind && includeKinds.some(k => action.kind?.startsWith(k));
     });
+  }
+
+  return { actions, filteredActions };
+}
+
+async function handleGetCodeActions({
+  root,
+  filePath,
+  startLine,
+  endLine,
+  includeKinds,
+}: z.infer<typeof schema>): Promise<string> {
+  const client = getLSPClient();
+  if (!client) {
+    throw new Error("LSP client not initialized");
+  }
+
+  const target = await resolveCodeActionTarget(root, filePath, startLine, endLine);
+  const { fileUri, content, startLineIndex, endLineIndex } = target;
+
+  return withCodeActionDocument(client, fileUri, content, async () => {
+    const { actions, filteredActions } = await requestCodeActions(
+      client,
+      target,
+      includeKinds
+    );
 
     if (actions.length === 0) {
       return `No code actions available for ${filePath}:${startLineIndex + 1}-${endLineIndex + 1}`;
     }
 
-    // Filter by kinds if specified
-    let filteredActions = actions;
-    if (includeKinds && includeKinds.length > 0) {
-      filteredActions = actions.filter(action => {
-        if (isCommand(action)) {
-          // Commands don't have kinds, so exclude them when filtering
-          return false;
-        }
-        return action.kind && includeKinds.some(k => action.kind?.startsWith(k));
-      });
-    }
-
     if (filteredActions.length === 0) {
       return `No code actions matching the specified kinds found for ${filePath}:${startLineIndex + 1}-${endLineIndex + 1}`;
     }
@@ -190,7 +259,7 @@ async function handleGetCodeActions({
       grouped.get(kind)!.push(action);
     }
 
-    // Format the code actions
+    // Format the code actions, numbered for lsmcp_apply_code_action
     let result = `Code actions for ${filePath}:${startLineIndex + 1}-${endLineIndex + 1}:\n\n`;
     
     for (const [kind, kindActions] of grouped) {
@@ -198,15 +267,13 @@ async function handleGetCodeActions({
       result += `=== ${kindName} ===\n`;
       
       for (const action of kindActions) {
-        result += formatCodeAction(action) + "\n\n";
+        const index = filteredActions.indexOf(action) + 1;
+        result += `${index}. ${formatCodeAction(action)}\n\n`;
       }
     }
 
     return result.trim();
-  } finally {
-    // Close the document
-    client.closeDocument(fileUri);
-  }
+  });
 }
 
 export const lspGetCodeActionsTool: ToolDef<typeof schema> = {
@@ -2,8 +2,14 @@ import { z } from "zod";
 import { type Result, ok, err } from "neverthrow";
 import { readFileSync } from "fs";
 import path from "path";
-import { getActiveClient } from "../lspClient.ts";
+import {
+  getActiveClient,
+  DEFAULT_DIAGNOSTICS_TIMEOUT,
+  DEFAULT_DIAGNOSTICS_SETTLE_TIME,
+} from "../lspClient.ts";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { waitForIdleShape, waitForIdleIfRequested } from "./lspCommon.ts";
+import { mergeDiagnostics, runCargoCheck } from "../../rust/cargoCheck.ts";
 
 const schema = z.object({
   root: z.string().describe("Root directory for resolving relative paths"),
@@ -14,11 +20,30 @@ const schema = z.object({
     .string()
     .optional()
     .describe("Virtual content to use for diagnostics instead of file content"),
+  timeout: z
+    .number()
+    .optional()
+    .describe(
+      `Maximum time in milliseconds to wait for diagnostics (default: ${DEFAULT_DIAGNOSTICS_TIMEOUT})`
+    ),
+  settleTime: z
+    .number()
+    .optional()
+    .describe(
+      `Return once no new diagnostics arrive for this many milliseconds (default: ${DEFAULT_DIAGNOSTICS_SETTLE_TIME})`
+    ),
+  cargoCheck: z
+    .enum(["check", "clippy"])
+    .optional()
+    .describe(
+      "Rust only: also run `cargo check` or `cargo clippy` on the saved files and merge compiler diagnostics (not combinable with virtualContent)"
+    ),
+  ...waitForIdleShape,
 });
 
 type GetDiagnosticsRequest = z.infer<typeof schema>;
 
-interface Diagnostic {
+export interface Diagnostic {
   severity: "error" | "warning" | "information" | "hint";
   line: number;
   column: number;
@@ -35,14 +60,14 @@ interface GetDiagnosticsSuccess {
 }
 
 // LSP Diagnostic severity mapping
-const SEVERITY_MAP: Record<number, Diagnostic["severity"]> = {
+export const SEVERITY_MAP: Record<number, Diagnostic["severity"]> = {
   1: "error",
   2: "warning",
   3: "information",
   4: "hint",
 };
 
-interface LSPDiagnostic {
+export interface LSPDiagnostic {
   range: {
     start: { line: number; character: number };
     end: { line: number; character: number };
@@ -53,12 +78,33 @@ interface LSPDiagnostic {
   message: string;
 }
 
+/**
+ * Convert an LSP diagnostic to our 1-based format
+ */
+export function toDiagnostic(diag: LSPDiagnostic): Diagnostic {
+  return {
+    severity: SEVERITY_MAP[diag.severity ?? 1] ?? "error",
+    line: diag.range.start.line + 1, // Convert to 1-based
+    column: diag.range.start.character + 1,
+    endLine: diag.range.end.line + 1,
+    endColumn: diag.range.end.character + 1,
+    message: diag.message,
+    source: diag.source,
+    code: diag.code,
+  };
+}
+
 /**
  * Gets diagnostics for a TypeScript file using LSP
  */
 async function getDiagnosticsWithLSP(
   request: GetDiagnosticsRequest
 ): Promise<Result<GetDiagnosticsSuccess, string>> {
+  // cargo only sees the files on disk, so its line numbers would not match virtual content
+  if (request.cargoCheck && request.virtualContent !== undefined) {
+    return err("cargoCheck checks the saved files and cannot be combined with virtualContent");
+  }
+
   try {
     const client = getActiveClient();
 
@@ -68,50 +114,50 @@ async function getDiagnosticsWithLSP(
       request.virtualContent || readFileSync(absolutePath, "utf-8");
     const fileUri = `file://${absolutePath}`;
 
+    // Prefer pull diagnostics (LSP 3.17); otherwise listen for
+    // publishDiagnostics before opening so the first publish is not missed
+    const supportsPull = !!client.getServerCapabilities()?.diagnosticProvider;
+    const published = supportsPull
+      ? null
+      : client.waitForDiagnostics(fileUri, {
+          timeout: request.timeout,
+          settleTime: request.settleTime,
+        });
+
     // Open document in LSP
     client.openDocument(fileUri, fileContent);
 
-    // Give LSP server time to process and compute diagnostics
-    await new Promise<void>((resolve) => setTimeout(resolve, 1500));
-
-    // Get diagnostics from LSP
-    const lspDiagnostics = client.getDiagnostics(fileUri) as LSPDiagnostic[];
-
-    // If using virtual content and no diagnostics yet, update the document
-    // to trigger diagnostics
-    if (request.virtualContent && lspDiagnostics.length === 0) {
-      client.updateDocument(fileUri, fileContent, 2);
-      await new Promise<void>((resolve) => setTimeout(resolve, 1000));
-      const updatedDiagnostics = client.getDiagnostics(
-        fileUri
+    let lspDiagnostics: LSPDiagnostic[];
+    try {
+      lspDiagnostics = (
+        published
+          ? await published
+          : (await client.pullDiagnostics(fileUri)) ?? []
       ) as LSPDiagnostic[];
-      if (updatedDiagnostics.length > 0) {
-        lspDiagnostics.push(...updatedDiagnostics);
-      }
+    } finally {
+      // Close the document so the next request gets fresh diagnostics
+      client.closeDocument(fileUri);
     }
 
     // Convert LSP diagnostics to our format
-    const diagnostics: Diagnostic[] = lspDiagnostics.map((diag) => ({
-      severity: SEVERITY_MAP[diag.severity ?? 1] ?? "error",
-      line: diag.range.start.line + 1, // Convert to 1-based
-      column: diag.range.start.character + 1,
-      endLine: diag.range.end.line + 1,
-      endColumn: diag.range.end.character + 1,
-      message: diag.message,
-      source: diag.source,
-      code: diag.code,
-    }));
+    let diagnostics: Diagnostic[] = lspDiagnostics.map(toDiagnostic);
+
+    if (request.cargoCheck) {
+      const compilerDiagnostics = await runCargoCheck(
+        path.dirname(absolutePath),
+        request.cargoCheck
+      );
+      diagnostics = mergeDiagnostics(
+        diagnostics,
+        compilerDiagnostics.get(absolutePath) ?? []
+      );
+    }
 
     const errorCount = diagnostics.filter((d) => d.severity === "error").length;
     const warningCount = diagnostics.filter(
       (d) => d.severity === "warning"
     ).length;
 
-    // Close the document if using virtual content to avoid caching issues
-    if (request.virtualContent) {
-      client.closeDocument(fileUri);
-    }
-
     return ok({
       message: `Found ${errorCount} error${
         errorCount !== 1 ? "s" : ""
@@ -131,6 +177,7 @@ export const lspGetDiagnosticsTool: ToolDef<typeof schema> = {
     "Get diagnostics (errors, warnings) for a file using LSP",
   schema,
   execute: async (args: z.infer<typeof schema>) => {
+    await waitForIdleIfRequested(args.waitForIdle);
     const result = await getDiagnosticsWithLSP(args);
     if (result.isOk()) {
       const messages = [result.value.message];
@@ -242,6 +289,29 @@ if (import.meta.vitest) {
       ).rejects.toThrow("ENOENT");
     });
 
+    it("should reject cargoCheck for virtual content", async () => {
+      await expect(
+        lspGetDiagnosticsTool.execute({
+          root,
+          filePath: "src/main.rs",
+          virtualContent: "fn main() {}",
+          cargoCheck: "check",
+        })
+      ).rejects.toThrow("cannot be combined with virtualContent");
+    });
+
+    it("should stop waiting after the timeout when nothing is published", async () => {
+      const client = getActiveClient();
+      const start = Date.now();
+      const diagnostics = await client.waitForDiagnostics(
+        "file:///non-existent-file-12345.ts",
+        { timeout: 200 }
+      );
+
+      expect(diagnostics).toEqual([]);
+      expect(Date.now() - start).toBeLessThan(2000);
+    });
+
     it("should get diagnostics for actual file", async () => {
       const result = await lspGetDiagnosticsTool.execute({
         root,
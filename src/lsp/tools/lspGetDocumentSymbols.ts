@@ -8,7 +8,7 @@ import { getLSPClient } from "../lspClient.ts";
 
 const schema = fileLocationSchema;
 
-function getSymbolKindName(kind: SymbolKind): string {
+export function getSymbolKindName(kind: SymbolKind): string {
   const symbolKindNames: Record<SymbolKind, string> = {
     [SymbolKind.File]: "File",
     [SymbolKind.Module]: "Module",
@@ -0,0 +1,221 @@
+import { z } from "zod";
+import type { FoldingRange, SelectionRange } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { debug } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  withLSPDocument,
+} from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File path (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  target: z
+    .string()
+    .describe("Text on the line to start from (defaults to the start of the line)")
+    .optional(),
+  level: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe("Which enclosing range to return: 1 for the innermost (default), 2 for the next outer, ..."),
+});
+
+type GetEnclosingRangeRequest = z.infer<typeof schema>;
+
+// 0-based, inclusive
+export interface LineRange {
+  startLine: number;
+  endLine: number;
+}
+
+/**
+ * Find the multi-line blocks (functions, impls, classes, ...) enclosing a line,
+ * innermost first.
+ *
+ * Selection ranges give the syntactic nesting, including doc comments and
+ * attributes of items; a range counts as a block when a code folding range ends
+ * on its last line. Without selection ranges, the folding ranges are used directly.
+ */
+export function findEnclosingRanges(
+  selection: SelectionRange | undefined,
+  foldingRanges: FoldingRange[],
+  line: number,
+  lineCount: number
+): LineRange[] {
+  const codeFolds = foldingRanges.filter(
+    (fold) => fold.kind !== "comment" && fold.kind !== "imports"
+  );
+  const ranges: LineRange[] = [];
+  const push = (range: LineRange) => {
+    const previous = ranges[ranges.length - 1];
+    // An item and its body end on the same line; keep the outer one
+    if (previous && previous.endLine === range.endLine) {
+      ranges[ranges.length - 1] = range;
+    } else {
+      ranges.push(range);
+    }
+  };
+
+  if (!selection) {
+    codeFolds
+      .filter((fold) => fold.startLine <= line && line <= fold.endLine)
+      .sort((a, b) => a.endLine - a.startLine - (b.endLine - b.startLine))
+      .forEach((fold) => push({ startLine: fold.startLine, endLine: fold.endLine }));
+    return ranges;
+  }
+
+  for (let current: SelectionRange | undefined = selection; current; current = current.parent) {
+    const { start, end } = current.range;
+    // Skip single lines and the whole document
+    if (end.line <= start.line || (start.line === 0 && end.line >= lineCount - 1)) {
+      continue;
+    }
+    const isBlock = codeFolds.some(
+      (fold) =>
+        start.line <= fold.startLine &&
+        fold.startLine <= line &&
+        // Servers folding whole lines only end the range before the closing line
+        (fold.endLine === end.line || fold.endLine === end.line - 1)
+    );
+    if (isBlock) {
+      push({ startLine: start.line, endLine: end.line });
+    }
+  }
+  return ranges;
+}
+
+async function handleGetEnclosingRange({
+  root,
+  filePath,
+  line,
+  target,
+  level = 1,
+}: GetEnclosingRangeRequest): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+  const lines = content.split("\n");
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    const client = getLSPClient()!;
+
+    // Either request may be unsupported; one of them is enough
+    const [selection] = await client
+      .getSelectionRanges(fileUri, [position])
+      .catch((error) => {
+        debug(`[lsp] selectionRange failed: ${error}`);
+        return [];
+      });
+    const foldingRanges = await client.getFoldingRanges(fileUri).catch((error) => {
+      debug(`[lsp] foldingRange failed: ${error}`);
+      return [];
+    });
+    if (!selection && foldingRanges.length === 0) {
+      throw new Error(
+        "The language server supports neither selection ranges nor folding ranges"
+      );
+    }
+
+    const ranges = findEnclosingRanges(selection, foldingRanges, position.line, lines.length);
+    if (ranges.length === 0) {
+      return `No enclosing block found for ${filePath}:${position.line + 1}`;
+    }
+    const range = ranges[level - 1];
+    if (!range) {
+      throw new Error(
+        `Level ${level} is out of range: ${filePath}:${position.line + 1} has ${ranges.length} enclosing block${ranges.length === 1 ? "" : "s"}`
+      );
+    }
+
+    const output = [
+      `${filePath}:${range.startLine + 1}-${range.endLine + 1} (level ${level} of ${ranges.length})`,
+      "",
+      ...lines
+        .slice(range.startLine, range.endLine + 1)
+        .map((text, i) => `${range.startLine + i + 1}: ${text}`),
+    ];
+
+    const outer = ranges.slice(level);
+    if (outer.length > 0) {
+      output.push("", "Outer blocks:");
+      outer.forEach((outerRange, i) => {
+        output.push(
+          `  level ${level + i + 1}: lines ${outerRange.startLine + 1}-${outerRange.endLine + 1} - ${lines[outerRange.startLine].trim()}`
+        );
+      });
+    }
+
+    return output.join("\n");
+  });
+}
+
+export const lspGetEnclosingRangeTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_enclosing_range",
+  description:
+    "Get the source of the block (function, impl, class, ...) enclosing a line, using LSP selection and folding ranges",
+  schema,
+  execute: async (args) => {
+    return handleGetEnclosingRange(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  // Builds a selection range chain from innermost to outermost [start, end] lines
+  const chain = (...lineRanges: [number, number][]): SelectionRange | undefined =>
+    lineRanges.reduceRight<SelectionRange | undefined>(
+      (parent, [startLine, endLine]) => ({
+        range: {
+          start: { line: startLine, character: 0 },
+          end: { line: endLine, character: 1 },
+        },
+        parent,
+      }),
+      undefined
+    );
+
+  describe("findEnclosingRanges", () => {
+    // 0: /// A simple calculator      (lines of examples/rust-project/src/lib.rs, 0-based)
+    // 5: impl Calculator {
+    // 11: /// Adds a number
+    // 12: pub fn add(&mut self, num: f64) -> &mut Self {
+    // 13:     self.value += num;
+    // 15: }
+    // 27: }
+    const folds: FoldingRange[] = [
+      { startLine: 5, endLine: 27 },
+      { startLine: 12, endLine: 15 },
+      { startLine: 11, endLine: 11, kind: "comment" },
+    ];
+
+    it("should return the item with its doc comment, then the impl", () => {
+      const selection = chain(
+        [13, 13], // self.value += num
+        [12, 15], // fn body block
+        [11, 15], // fn item with doc comment
+        [5, 27], // impl item list
+        [5, 27], // impl item
+        [0, 50] // source file
+      );
+
+      expect(findEnclosingRanges(selection, folds, 13, 51)).toEqual([
+        { startLine: 11, endLine: 15 },
+        { startLine: 5, endLine: 27 },
+      ]);
+    });
+
+    it("should use folding ranges when selection ranges are unavailable", () => {
+      expect(findEnclosingRanges(undefined, folds, 13, 51)).toEqual([
+        { startLine: 12, endLine: 15 },
+        { startLine: 5, endLine: 27 },
+      ]);
+    });
+  });
+}
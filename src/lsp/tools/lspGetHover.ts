@@ -2,11 +2,17 @@ import { z } from "zod";
 import { type Result, ok, err } from "neverthrow";
 import { getActiveClient } from "../lspClient.ts";
 import { parseLineNumber } from "../../textUtils/parseLineNumber.ts";
-import { findSymbolInLine } from "../../textUtils/findSymbolInLine.ts";
 import { findTargetInFile } from "../../textUtils/findTargetInFile.ts";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
+import {
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
 import { readFileSync } from "fs";
 import path from "path";
+import { pathToFileURL } from "url";
 
 const schema = z.object({
   root: z.string().describe("Root directory for resolving relative paths"),
@@ -22,6 +28,7 @@ const schema = z.object({
     .describe("Character position in the line (0-based)")
     .optional(),
   target: z.string().describe("Text to find and get hover information for").optional(),
+  ...waitForIdleShape,
 });
 
 type GetHoverRequest = z.infer<typeof schema>;
@@ -56,43 +63,34 @@ interface GetHoverSuccess {
 }
 
 /**
- * Helper to handle hover request when line is not provided
+ * Locate the hover position, skipping target matches in strings and comments.
+ * The document must already be open.
+ * @param lineNumber 1-based line, or undefined to search the whole file for the target
  */
-async function getHoverWithoutLine(
-  request: GetHoverRequest
-): Promise<Result<GetHoverSuccess, string>> {
-  try {
-    const client = getActiveClient();
-
-    // Read file content
-    const absolutePath = path.resolve(request.root, request.filePath);
-    const fileContent = readFileSync(absolutePath, "utf-8");
-    const fileUri = `file://${absolutePath}`;
-    const lines = fileContent.split("\n");
-
-    // Find target text in file
-    const targetResult = findTargetInFile(lines, request.target || "");
+async function findHoverPosition(
+  request: GetHoverRequest,
+  fileUri: string,
+  fileContent: string,
+  lineNumber: number | undefined
+): Promise<{ line: number; character: number }> {
+  if (lineNumber === undefined) {
+    const tokens = await getActiveClient().getSemanticTokens(fileUri);
+    const targetResult = findTargetInFile(
+      fileContent.split("\n"),
+      request.target || "",
+      tokens ?? undefined
+    );
     if ("error" in targetResult) {
-      return err(`${targetResult.error} in ${request.filePath}`);
+      throw new Error(`${targetResult.error} in ${request.filePath}`);
     }
+    return { line: targetResult.lineIndex, character: targetResult.characterIndex };
+  }
 
-    const { lineIndex: targetLine, characterIndex: symbolPosition } =
-      targetResult;
-
-    // Open document in LSP
-    client.openDocument(fileUri, fileContent);
-    await new Promise<void>((resolve) => setTimeout(resolve, 1000));
-
-    // Get hover info
-    const result = (await client.getHover(fileUri, {
-      line: targetLine,
-      character: symbolPosition,
-    })) as HoverResult | null;
-
-    return formatHoverResult(result, request, targetLine, symbolPosition);
-  } catch (error) {
-    return err(error instanceof Error ? error.message : String(error));
+  if (request.character !== undefined) {
+    return { line: lineNumber - 1, character: request.character };
   }
+  // Without a target, this is the first non-whitespace character of the line
+  return resolveTargetPosition(fileUri, fileContent, lineNumber, request.target, request.filePath);
 }
 
 /**
@@ -163,58 +161,25 @@ function formatHoverResult(
 async function getHover(
   request: GetHoverRequest
 ): Promise<Result<GetHoverSuccess, string>> {
-  // If line is not provided, we need to find the target text
-  if (request.line === undefined) {
-    return getHoverWithoutLine(request);
-  }
-
   try {
-    const client = getActiveClient();
-    
-    // Read file content
     const absolutePath = path.resolve(request.root, request.filePath);
     const fileContent = readFileSync(absolutePath, "utf-8");
-    const fileUri = `file://${absolutePath}`;
-    
-    // Parse line number
-    const lines = fileContent.split("\n");
-    const lineResult = parseLineNumber(fileContent, request.line);
-    if ("error" in lineResult) {
-      return err(`${lineResult.error} in ${request.filePath}`);
-    }
-    
-    const targetLine = lineResult.lineIndex;
-    
-    // Determine character position
-    let symbolPosition: number;
-    if (request.character !== undefined) {
-      // Use provided character position
-      symbolPosition = request.character;
-    } else if (request.target) {
-      // Find symbol position in line
-      const lineText = lines[targetLine];
-      const symbolResult = findSymbolInLine(lineText, request.target);
-      if ("error" in symbolResult) {
-        return err(`${symbolResult.error} on line ${targetLine + 1}`);
+    const fileUri = pathToFileURL(absolutePath).toString();
+
+    let lineNumber: number | undefined;
+    if (request.line !== undefined) {
+      const lineResult = parseLineNumber(fileContent, request.line);
+      if ("error" in lineResult) {
+        return err(`${lineResult.error} in ${request.filePath}`);
       }
-      symbolPosition = symbolResult.characterIndex;
-    } else {
-      // Default to beginning of line if neither character nor target is provided
-      symbolPosition = 0;
+      lineNumber = lineResult.lineIndex + 1;
     }
-    
-    // Open document in LSP
-    client.openDocument(fileUri, fileContent);
-    
-    // Give LSP server time to process the document
-    await new Promise<void>((resolve) => setTimeout(resolve, 1000));
-    // Get hover info
-    const result = (await client.getHover(fileUri, {
-      line: targetLine,
-      character: symbolPosition,
-    })) as HoverResult | null;
-
-    return formatHoverResult(result, request, targetLine, symbolPosition);
+
+    return await withLSPDocument(fileUri, fileContent, async () => {
+      const position = await findHoverPosition(request, fileUri, fileContent, lineNumber);
+      const result = (await getActiveClient().getHover(fileUri, position)) as HoverResult | null;
+      return formatHoverResult(result, request, position.line, position.character);
+    });
   } catch (error) {
     return err(error instanceof Error ? error.message : String(error));
   }
@@ -250,6 +215,7 @@ export const lspGetHoverTool: ToolDef<typeof schema> = {
     "Get hover information (type signature, documentation) for a symbol using LSP",
   schema,
   execute: async (args: z.infer<typeof schema>) => {
+    await waitForIdleIfRequested(args.waitForIdle);
     const result = await getHover(args);
     if (result.isOk()) {
       const messages = [result.value.message];
@@ -0,0 +1,161 @@
+import { z } from "zod";
+import type { InlayHint } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import {
+  prepareFileContext,
+  resolveLineOrThrow,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File path (relative to root)"),
+  startLine: z
+    .union([z.number(), z.string()])
+    .describe("First line (1-based) or string to match (defaults to the start of the file)")
+    .optional(),
+  endLine: z
+    .union([z.number(), z.string()])
+    .describe("Last line (1-based) or string to match (defaults to the end of the file)")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+type GetInlayHintsRequest = z.infer<typeof schema>;
+
+function hintLabel(hint: InlayHint): string {
+  const label =
+    typeof hint.label === "string"
+      ? hint.label
+      : hint.label.map((part) => part.value).join("");
+  return `${hint.paddingLeft ? " " : ""}${label}${hint.paddingRight ? " " : ""}`;
+}
+
+/**
+ * Render lines [startLine, endLine] (0-based) with hint labels inserted at
+ * their positions, prefixed by 1-based line numbers
+ */
+export function renderInlayHints(
+  lines: string[],
+  hints: InlayHint[],
+  startLine: number,
+  endLine: number
+): string[] {
+  const rendered: string[] = [];
+  for (let i = startLine; i <= endLine; i++) {
+    let text = lines[i] ?? "";
+    // Insert from the right so earlier positions stay valid; reversing keeps
+    // hints at the same position in server order
+    const lineHints = hints
+      .filter((hint) => hint.position.line === i)
+      .reverse()
+      .sort((a, b) => b.position.character - a.position.character);
+    for (const hint of lineHints) {
+      const at = Math.min(hint.position.character, text.length);
+      text = text.slice(0, at) + hintLabel(hint) + text.slice(at);
+    }
+    rendered.push(`${i + 1}: ${text}`);
+  }
+  return rendered;
+}
+
+async function handleGetInlayHints({
+  root,
+  filePath,
+  startLine,
+  endLine,
+  waitForIdle,
+}: GetInlayHintsRequest): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+  const lines = content.split("\n");
+
+  const start = startLine !== undefined ? resolveLineOrThrow(content, startLine, filePath) : 0;
+  const end =
+    endLine !== undefined ? resolveLineOrThrow(content, endLine, filePath) : lines.length - 1;
+  if (end < start) {
+    throw new Error(`endLine (${end + 1}) is before startLine (${start + 1})`);
+  }
+
+  return withLSPDocument(fileUri, content, async () => {
+    await waitForIdleIfRequested(waitForIdle);
+    const client = getLSPClient()!;
+
+    const hints = await client.getInlayHints(fileUri, {
+      start: { line: start, character: 0 },
+      end: { line: end, character: lines[end].length },
+    });
+
+    return [
+      `Inlay hints for ${filePath}:${start + 1}-${end + 1} (${hints.length} hint${hints.length === 1 ? "" : "s"})`,
+      "",
+      ...renderInlayHints(lines, hints, start, end),
+    ].join("\n");
+  });
+}
+
+export const lspGetInlayHintsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_inlay_hints",
+  description:
+    "Show a file range annotated with inlay hints (inferred types, parameter names) from the language server",
+  schema,
+  execute: async (args) => {
+    return handleGetInlayHints(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("renderInlayHints", () => {
+    const lines = [
+      "fn main() {",
+      "    let mut calc = Calculator::new();",
+      '    let message = greet("Rust MCP");',
+      "}",
+    ];
+
+    it("should insert type and parameter hints inline", () => {
+      const rendered = renderInlayHints(
+        lines,
+        [
+          { position: { line: 1, character: 16 }, label: ": Calculator", kind: 1 },
+          {
+            position: { line: 2, character: 15 },
+            label: [{ value: ": " }, { value: "String" }],
+            kind: 1,
+          },
+          {
+            position: { line: 2, character: 24 },
+            label: "name:",
+            kind: 2,
+            paddingRight: true,
+          },
+        ],
+        1,
+        2
+      );
+
+      expect(rendered).toEqual([
+        "2:     let mut calc: Calculator = Calculator::new();",
+        '3:     let message: String = greet(name: "Rust MCP");',
+      ]);
+    });
+
+    it("should keep server order for hints at the same position", () => {
+      const rendered = renderInlayHints(
+        ["x"],
+        [
+          { position: { line: 0, character: 1 }, label: "a" },
+          { position: { line: 0, character: 1 }, label: "b" },
+        ],
+        0,
+        0
+      );
+
+      expect(rendered).toEqual(["1: xab"]);
+    });
+  });
+}
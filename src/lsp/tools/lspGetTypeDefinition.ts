@@ -0,0 +1,60 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import { formatNavigationTargets, toNavigationTargets } from "../navigationTargets.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File path containing the symbol (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  symbolName: z
+    .string()
+    .describe("Name of the variable or expression to get the type definition of"),
+  ...waitForIdleShape,
+});
+
+async function handleGetTypeDefinition({
+  root,
+  filePath,
+  line,
+  symbolName,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, symbolName, filePath);
+    await waitForIdleIfRequested(waitForIdle);
+    const client = getLSPClient()!;
+
+    const targets = toNavigationTargets(
+      await client.getTypeDefinition(fileUri, position)
+    );
+    return formatNavigationTargets(
+      root,
+      `Found ${targets.length} type definition${targets.length === 1 ? "" : "s"} for "${symbolName}"`,
+      targets
+    );
+  });
+}
+
+export const lspGetTypeDefinitionTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_type_definition",
+  description:
+    "Go to the definition of the type of a symbol (e.g. the struct or interface of a variable) using LSP",
+  schema,
+  execute: async (args) => {
+    return handleGetTypeDefinition(args);
+  },
+};
@@ -0,0 +1,87 @@
+import { z } from "zod";
+import type { TypeHierarchyItem } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+import { depthSchema, directionSchema, formatHierarchies } from "../hierarchyTree.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File path containing the symbol (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  symbolName: z.string().describe("Name of the type on the line"),
+  direction: directionSchema(["supertypes", "subtypes"], "Show supertypes, subtypes or both"),
+  depth: depthSchema("the hierarchy"),
+  ...waitForIdleShape,
+});
+
+type GetTypeHierarchyRequest = z.infer<typeof schema>;
+
+async function handleGetTypeHierarchy({
+  root,
+  filePath,
+  line,
+  symbolName,
+  direction,
+  depth,
+  waitForIdle,
+}: GetTypeHierarchyRequest): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, symbolName, filePath);
+    await waitForIdleIfRequested(waitForIdle);
+    const client = getLSPClient()!;
+
+    if (!client.getServerCapabilities()?.typeHierarchyProvider) {
+      return "The language server does not support type hierarchy. Use lsmcp_find_implementations to find implementations instead.";
+    }
+
+    const items = await client.prepareTypeHierarchy(fileUri, position);
+    if (items.length === 0) {
+      return `No type found for "${symbolName}" at ${filePath}:${position.line + 1}:${position.character + 1}`;
+    }
+
+    const related = (types: TypeHierarchyItem[]) => types.map((item) => ({ item }));
+    return formatHierarchies(
+      root,
+      "Type hierarchy",
+      items,
+      [
+        {
+          name: "supertypes",
+          heading: "Supertypes:",
+          getRelated: async (item) => related(await client.getSupertypes(item)),
+        },
+        {
+          name: "subtypes",
+          heading: "Subtypes:",
+          getRelated: async (item) => related(await client.getSubtypes(item)),
+        },
+      ],
+      direction,
+      depth
+    );
+  });
+}
+
+export const lspGetTypeHierarchyTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_type_hierarchy",
+  description:
+    "Show the supertypes and/or subtypes of a class, interface or trait as a depth-limited tree using LSP",
+  schema,
+  execute: async (args) => {
+    return handleGetTypeHierarchy(args);
+  },
+};
+
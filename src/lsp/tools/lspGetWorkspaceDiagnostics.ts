@@ -0,0 +1,301 @@
+import { z } from "zod";
+import { type Result, ok, err } from "neverthrow";
+import { readFileSync } from "fs";
+import path from "path";
+import { pathToFileURL } from "url";
+import { glob } from "glob";
+import { getActiveClient } from "../lspClient.ts";
+import {
+  toDiagnostic,
+  type Diagnostic,
+  type LSPDiagnostic,
+} from "./lspGetDiagnostics.ts";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { waitForIdleShape, waitForIdleIfRequested } from "./lspCommon.ts";
+import { mergeDiagnostics, runCargoCheck } from "../../rust/cargoCheck.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  pattern: z
+    .string()
+    .describe('Glob pattern for files to check (e.g., "src/**/*.rs", "**/*.ts")'),
+  maxFiles: z
+    .number()
+    .optional()
+    .default(200)
+    .describe("Maximum number of files to open when the server has no workspace diagnostics"),
+  timeout: z
+    .number()
+    .optional()
+    .describe("Maximum time in milliseconds to wait for each file's diagnostics"),
+  settleTime: z
+    .number()
+    .optional()
+    .describe("Return once no new diagnostics arrive for this many milliseconds"),
+  cargoCheck: z
+    .enum(["check", "clippy"])
+    .optional()
+    .describe(
+      "Rust only: also run `cargo check` or `cargo clippy` and merge compiler diagnostics"
+    ),
+  ...waitForIdleShape,
+});
+
+type GetWorkspaceDiagnosticsRequest = z.infer<typeof schema>;
+
+interface FileDiagnostics {
+  filePath: string;
+  diagnostics: Diagnostic[];
+}
+
+interface GetWorkspaceDiagnosticsSuccess {
+  source: string;
+  checkedFiles: number;
+  truncated: boolean;
+  files: FileDiagnostics[];
+}
+
+export const IGNORE_PATTERNS = [
+  "**/node_modules/**",
+  "**/target/**",
+  "**/dist/**",
+  "**/.git/**",
+];
+
+const SEVERITIES: Diagnostic["severity"][] = [
+  "error",
+  "warning",
+  "information",
+  "hint",
+];
+
+/**
+ * Merge cargo check/clippy diagnostics into the LSP results when requested
+ */
+async function withCargoDiagnostics(
+  request: GetWorkspaceDiagnosticsRequest,
+  result: GetWorkspaceDiagnosticsSuccess
+): Promise<GetWorkspaceDiagnosticsSuccess> {
+  if (!request.cargoCheck) {
+    return result;
+  }
+  const compilerDiagnostics = await runCargoCheck(request.root, request.cargoCheck);
+  return {
+    ...result,
+    source: `${result.source} + cargo ${request.cargoCheck}`,
+    files: result.files.map((file) => ({
+      filePath: file.filePath,
+      diagnostics: mergeDiagnostics(
+        file.diagnostics,
+        compilerDiagnostics.get(path.resolve(request.root, file.filePath)) ?? []
+      ),
+    })),
+  };
+}
+
+/**
+ * Collects diagnostics for every file matching a glob, using workspace/diagnostic
+ * when the server supports it and opening each file otherwise
+ */
+async function getWorkspaceDiagnostics(
+  request: GetWorkspaceDiagnosticsRequest
+): Promise<Result<GetWorkspaceDiagnosticsSuccess, string>> {
+  try {
+    const client = getActiveClient();
+
+    const matches = await glob(request.pattern, {
+      cwd: request.root,
+      ignore: IGNORE_PATTERNS,
+      absolute: true,
+      nodir: true,
+    });
+    matches.sort();
+
+    if (matches.length === 0) {
+      return err(`No files found matching pattern: ${request.pattern}`);
+    }
+
+    // Prefer a single workspace/diagnostic request (LSP 3.17)
+    const workspaceDiagnostics = await client.pullWorkspaceDiagnostics();
+    if (workspaceDiagnostics) {
+      const files = matches.map((absolutePath) => ({
+        filePath: path.relative(request.root, absolutePath),
+        diagnostics: (
+          workspaceDiagnostics.get(pathToFileURL(absolutePath).toString()) ?? []
+        ).map((d) => toDiagnostic(d as LSPDiagnostic)),
+      }));
+      return ok(
+        await withCargoDiagnostics(request, {
+          source: "workspace/diagnostic",
+          checkedFiles: matches.length,
+          truncated: false,
+          files,
+        })
+      );
+    }
+
+    // Fall back to opening each file and collecting publishDiagnostics
+    const targets = matches.slice(0, request.maxFiles);
+    const uris = targets.map((absolutePath) => pathToFileURL(absolutePath).toString());
+    const pending = uris.map((uri) =>
+      client.waitForDiagnostics(uri, {
+        timeout: request.timeout,
+        settleTime: request.settleTime,
+      })
+    );
+
+    let results: LSPDiagnostic[][];
+    try {
+      targets.forEach((absolutePath, i) => {
+        client.openDocument(uris[i], readFileSync(absolutePath, "utf-8"));
+      });
+      results = (await Promise.all(pending)) as LSPDiagnostic[][];
+    } finally {
+      for (const uri of uris) {
+        client.closeDocument(uri);
+      }
+    }
+
+    const files = targets.map((absolutePath, i) => ({
+      filePath: path.relative(request.root, absolutePath),
+      diagnostics: results[i].map(toDiagnostic),
+    }));
+
+    return ok(
+      await withCargoDiagnostics(request, {
+        source: "publishDiagnostics",
+        checkedFiles: targets.length,
+        truncated: matches.length > targets.length,
+        files,
+      })
+    );
+  } catch (error) {
+    return err(error instanceof Error ? error.message : String(error));
+  }
+}
+
+function countBySeverity(diagnostics: Diagnostic[]): Record<Diagnostic["severity"], number> {
+  const counts = { error: 0, warning: 0, information: 0, hint: 0 };
+  for (const diag of diagnostics) {
+    counts[diag.severity]++;
+  }
+  return counts;
+}
+
+function formatCounts(counts: Record<Diagnostic["severity"], number>): string {
+  return SEVERITIES.filter((severity) => counts[severity] > 0)
+    .map((severity) => `${counts[severity]} ${severity}${counts[severity] !== 1 ? "s" : ""}`)
+    .join(", ");
+}
+
+/**
+ * Format workspace diagnostics grouped by file with per-severity counts
+ */
+function formatWorkspaceDiagnostics(
+  result: GetWorkspaceDiagnosticsSuccess
+): string {
+  const filesWithDiagnostics = result.files.filter(
+    (f) => f.diagnostics.length > 0
+  );
+  const total = countBySeverity(
+    result.files.flatMap((f) => f.diagnostics)
+  );
+
+  const lines = [
+    `Checked ${result.checkedFiles} file${result.checkedFiles !== 1 ? "s" : ""} (via ${result.source})`,
+    `Total: ${formatCounts(total) || "no diagnostics"}`,
+  ];
+  if (result.truncated) {
+    lines.push("Note: file list was truncated, increase maxFiles to check more files");
+  }
+
+  for (const file of filesWithDiagnostics) {
+    lines.push("", `${file.filePath}: ${formatCounts(countBySeverity(file.diagnostics))}`);
+    for (const diag of file.diagnostics) {
+      const codeInfo = diag.code ? ` [${diag.code}]` : "";
+      const sourceInfo = diag.source ? ` (${diag.source})` : "";
+      lines.push(
+        `  ${diag.severity.toUpperCase()} ${diag.line}:${diag.column} ${diag.message}${codeInfo}${sourceInfo}`
+      );
+    }
+  }
+
+  const cleanFiles = result.files.length - filesWithDiagnostics.length;
+  if (cleanFiles > 0) {
+    lines.push("", `${cleanFiles} file${cleanFiles !== 1 ? "s" : ""} without diagnostics`);
+  }
+
+  return lines.join("\n");
+}
+
+export const lspGetWorkspaceDiagnosticsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_get_workspace_diagnostics",
+  description:
+    "Get diagnostics (errors, warnings) for all files matching a glob pattern using LSP, grouped by file",
+  schema,
+  execute: async (args: z.infer<typeof schema>) => {
+    await waitForIdleIfRequested(args.waitForIdle);
+    const result = await getWorkspaceDiagnostics(args);
+    if (result.isErr()) {
+      throw new Error(result.error);
+    }
+    return formatWorkspaceDiagnostics(result.value);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("formatWorkspaceDiagnostics", () => {
+    const diag = (
+      severity: Diagnostic["severity"],
+      line: number,
+      message: string
+    ): Diagnostic => ({
+      severity,
+      line,
+      column: 1,
+      endLine: line,
+      endColumn: 2,
+      message,
+    });
+
+    it("should group diagnostics by file with severity counts", () => {
+      const output = formatWorkspaceDiagnostics({
+        source: "publishDiagnostics",
+        checkedFiles: 3,
+        truncated: false,
+        files: [
+          {
+            filePath: "src/errors.rs",
+            diagnostics: [
+              diag("error", 4, "mismatched types"),
+              diag("error", 8, "cannot find value"),
+              diag("warning", 18, "unused variable"),
+            ],
+          },
+          { filePath: "src/lib.rs", diagnostics: [] },
+          { filePath: "src/main.rs", diagnostics: [] },
+        ],
+      });
+
+      expect(output).toContain("Checked 3 files (via publishDiagnostics)");
+      expect(output).toContain("Total: 2 errors, 1 warning");
+      expect(output).toContain("src/errors.rs: 2 errors, 1 warning");
+      expect(output).toContain("ERROR 4:1 mismatched types");
+      expect(output).toContain("2 files without diagnostics");
+      expect(output).not.toContain("src/lib.rs:");
+    });
+
+    it("should note truncated file lists", () => {
+      const output = formatWorkspaceDiagnostics({
+        source: "publishDiagnostics",
+        checkedFiles: 1,
+        truncated: true,
+        files: [{ filePath: "a.ts", diagnostics: [] }],
+      });
+
+      expect(output).toContain("increase maxFiles");
+    });
+  });
+}
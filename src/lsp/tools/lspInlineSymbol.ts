@@ -0,0 +1,50 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { applyRefactor, type RefactorKind } from "./lspApplyCodeAction.ts";
+import { resolveTargetPosition } from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File path (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match in the line"),
+  target: z
+    .string()
+    .describe("Variable, function or call on the line to inline"),
+  title: z
+    .string()
+    .describe("Title (or unique substring) of the action when the server offers several, e.g. 'all callers'")
+    .optional(),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the inlining as a unified diff without modifying any files"),
+});
+
+const INLINE: RefactorKind = {
+  label: "inline",
+  kinds: ["refactor.inline"],
+  // "Inline variable", "Inline call", "Inline into all callers", ...
+  titlePattern: /^Inline\b/i,
+};
+
+export const lspInlineSymbolTool: ToolDef<typeof schema> = {
+  name: "lsmcp_inline_symbol",
+  description:
+    "Inline a variable, function or call at its use sites using the language server's inline refactoring, and show the diff",
+  schema,
+  execute: async ({ root, filePath, line, target, title, dryRun }) => {
+    return applyRefactor(
+      root,
+      filePath,
+      INLINE,
+      async (fileUri, content) => {
+        const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+        return { start: position, end: position };
+      },
+      title,
+      dryRun
+    );
+  },
+};
@@ -0,0 +1,107 @@
+import { z } from "zod";
+import path from "path";
+import { existsSync } from "fs";
+import { pathToFileURL } from "url";
+import {
+  OptionalVersionedTextDocumentIdentifier,
+  RenameFile,
+  TextDocumentEdit,
+  type WorkspaceEdit,
+} from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import {
+  applyWorkspaceEdit,
+  formatWorkspaceEditSummary,
+} from "../applyWorkspaceEdit.ts";
+import {
+  prepareFileContext,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "./lspCommon.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  oldPath: z.string().describe("Current file path (relative to root)"),
+  newPath: z.string().describe("New file path (relative to root)"),
+  overwrite: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe("Overwrite the destination file if it exists"),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the move as a unified diff without modifying any files"),
+  ...waitForIdleShape,
+});
+
+type MoveFileRequest = z.infer<typeof schema>;
+
+async function handleMoveFile({
+  root,
+  oldPath,
+  newPath,
+  overwrite,
+  dryRun,
+  waitForIdle,
+}: MoveFileRequest): Promise<string> {
+  const { absolutePath, fileUri: oldUri, content } = await prepareFileContext(root, oldPath);
+  const absoluteNewPath = path.isAbsolute(newPath) ? newPath : path.join(root, newPath);
+  if (existsSync(absoluteNewPath) && !overwrite) {
+    throw new Error(`Destination already exists: ${newPath}. Pass overwrite: true to replace it.`);
+  }
+  const newUri = pathToFileURL(absoluteNewPath).toString();
+  const files = [{ oldUri, newUri }];
+
+  const client = getLSPClient();
+  const referenceEdit = await withLSPDocument(oldUri, content, async () => {
+    // Module declarations and import paths are only known once the project is loaded
+    await waitForIdleIfRequested(waitForIdle);
+    return client!.willRenameFiles(files);
+  });
+
+  // documentChanges takes precedence over changes, so both are expressed as
+  // document changes. The server's edits refer to the files before the move,
+  // so the move comes last.
+  const referenceChanges =
+    referenceEdit?.documentChanges ??
+    Object.entries(referenceEdit?.changes ?? {}).map(([uri, edits]) =>
+      TextDocumentEdit.create(OptionalVersionedTextDocumentIdentifier.create(uri, null), edits)
+    );
+  const edit: WorkspaceEdit = {
+    documentChanges: [...referenceChanges, RenameFile.create(oldUri, newUri, { overwrite })],
+  };
+  const result = applyWorkspaceEdit(edit, { dryRun, root });
+  if (!dryRun) {
+    client!.didRenameFiles(files);
+  }
+
+  const updated = result.files.filter((file) => file.filePath !== absoluteNewPath);
+  const lines = [
+    dryRun
+      ? `Dry run: moving ${path.relative(root, absolutePath)} to ${newPath} would update ${updated.length} other file(s). No files were modified.`
+      : `Moved ${path.relative(root, absolutePath)} to ${newPath} and updated ${updated.length} other file(s)`,
+  ];
+  if (!client!.getServerCapabilities()?.workspace?.fileOperations?.willRename) {
+    lines.push(
+      "The language server does not support workspace/willRenameFiles, so references to the file were not updated."
+    );
+  }
+  lines.push("", formatWorkspaceEditSummary(result, root));
+  if (result.diff) {
+    lines.push("", result.diff);
+  }
+  return lines.join("\n");
+}
+
+export const lspMoveFileTool: ToolDef<typeof schema> = {
+  name: "lsmcp_move_file",
+  description:
+    "Move a file and let the language server update references to it (imports, `mod` declarations, `use` paths) via workspace/willRenameFiles",
+  schema,
+  execute: async (args) => {
+    return handleMoveFile(args);
+  },
+};
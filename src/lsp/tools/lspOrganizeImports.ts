@@ -0,0 +1,294 @@
+import { z } from "zod";
+import path from "path";
+import { glob } from "glob";
+import type { CodeAction, Command } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import type { LSPClient } from "../lspTypes.ts";
+import {
+  prepareFileContext,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+} from "./lspCommon.ts";
+import { runCodeAction } from "./lspApplyCodeAction.ts";
+import { isCommand, withCodeActionDocument } from "./lspGetCodeActions.ts";
+import { IGNORE_PATTERNS } from "./lspGetWorkspaceDiagnostics.ts";
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z
+    .string()
+    .describe("File to organize (relative to root)")
+    .optional(),
+  pattern: z
+    .string()
+    .describe('Glob pattern for files to organize instead of filePath (e.g., "src/**/*.rs")')
+    .optional(),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Report the import changes without modifying any files"),
+  ...waitForIdleShape,
+});
+
+type OrganizeImportsRequest = z.infer<typeof schema>;
+
+interface FileImportChanges {
+  filePath: string;
+  action: string;
+  added: string[];
+  removed: string[];
+}
+
+// Start of a Rust `use` item or a TypeScript/JavaScript import statement
+const IMPORT_START = /^\s*(?:(?:pub(?:\([^)]*\))?\s+)?use\s|import[\s{"'])/;
+
+/**
+ * Extract the import statements of a file, joining multi-line ones
+ */
+export function extractImports(content: string): string[] {
+  const lines = content.split("\n");
+  const braceDepth = (text: string) =>
+    (text.match(/\{/g)?.length ?? 0) - (text.match(/\}/g)?.length ?? 0);
+
+  const imports: string[] = [];
+  for (let i = 0; i < lines.length; i++) {
+    if (!IMPORT_START.test(lines[i])) {
+      continue;
+    }
+    let statement = lines[i];
+    let depth = braceDepth(statement);
+    while (depth > 0 && i + 1 < lines.length) {
+      i++;
+      statement += ` ${lines[i]}`;
+      depth += braceDepth(lines[i]);
+    }
+    imports.push(statement.replace(/\s+/g, " ").trim());
+  }
+  return imports;
+}
+
+/**
+ * Compare the import statements before and after an edit, ignoring formatting
+ * and the order of statements
+ */
+export function diffImports(
+  oldContent: string,
+  newContent: string
+): { added: string[]; removed: string[] } {
+  // `{ a, b, }` and `{a, b}` are the same import
+  const key = (statement: string) =>
+    statement.replace(/\s*([{},;])\s*/g, "$1").replace(/,\}/g, "}");
+  const oldImports = extractImports(oldContent);
+  const newImports = extractImports(newContent);
+  const oldKeys = new Set(oldImports.map(key));
+  const newKeys = new Set(newImports.map(key));
+
+  return {
+    added: newImports.filter((statement) => !oldKeys.has(key(statement))),
+    removed: oldImports.filter((statement) => !newKeys.has(key(statement))),
+  };
+}
+
+/**
+ * Find the action cleaning up a file's imports: a source.organizeImports action,
+ * or else a quick fix removing unused imports (rust-analyzer has no organize imports).
+ * Expects the document's diagnostics to be collected, see withCodeActionDocument.
+ */
+async function findOrganizeImportsAction(
+  client: LSPClient,
+  fileUri: string,
+  content: string
+): Promise<CodeAction | undefined> {
+  const lines = content.split("\n");
+  const range = {
+    start: { line: 0, character: 0 },
+    end: { line: lines.length - 1, character: lines[lines.length - 1].length },
+  };
+  const usable = (action: Command | CodeAction): action is CodeAction =>
+    !isCommand(action) && !action.disabled;
+
+  const organize = await client.getCodeActions(fileUri, range, {
+    only: ["source.organizeImports"],
+  });
+  const organizeAction = organize.find(
+    (action): action is CodeAction =>
+      usable(action) && action.kind?.startsWith("source.organizeImports") === true
+  );
+  if (organizeAction) {
+    return organizeAction;
+  }
+
+  const fixes = await client.getCodeActions(fileUri, range, {
+    diagnostics: client.getDiagnostics(fileUri),
+    only: ["quickfix"],
+  });
+  return selectUnusedImportsFix(fixes.filter(usable));
+}
+
+/**
+ * Pick the quick fix removing unused imports, preferring the one removing all of
+ * them (rust-analyzer's "Remove all unused imports") over a single import's fix
+ */
+function selectUnusedImportsFix(fixes: CodeAction[]): CodeAction | undefined {
+  const removals = fixes.filter((action) => /unused imports?\b/i.test(action.title));
+  return removals.find((action) => /\ball\b/i.test(action.title)) ?? removals[0];
+}
+
+async function organizeFile(
+  root: string,
+  absolutePath: string,
+  dryRun?: boolean
+): Promise<FileImportChanges | undefined> {
+  const { fileUri, content } = await prepareFileContext(root, absolutePath);
+
+  const client = getLSPClient();
+  if (!client) {
+    throw new Error("LSP client not initialized");
+  }
+
+  return withCodeActionDocument(client, fileUri, content, async () => {
+    const selected = await findOrganizeImportsAction(client, fileUri, content);
+    if (!selected) {
+      return undefined;
+    }
+
+    const { editResults } = await runCodeAction(client, selected, root, dryRun);
+    // A command may edit the file several times through workspace/applyEdit
+    const edits = editResults
+      .flatMap((result) => result.files)
+      .filter((changed) => changed.filePath === absolutePath);
+    if (edits.length === 0) {
+      return undefined;
+    }
+    return {
+      filePath: path.relative(root, absolutePath),
+      action: selected.title,
+      ...diffImports(edits[0].oldContent, edits[edits.length - 1].newContent),
+    };
+  });
+}
+
+async function handleOrganizeImports({
+  root,
+  filePath,
+  pattern,
+  dryRun,
+  waitForIdle,
+}: OrganizeImportsRequest): Promise<string> {
+  if ((filePath === undefined) === (pattern === undefined)) {
+    throw new Error("Specify either filePath or pattern");
+  }
+
+  const files = filePath
+    ? [path.resolve(root, filePath)]
+    : (
+        await glob(pattern!, {
+          cwd: root,
+          ignore: IGNORE_PATTERNS,
+          absolute: true,
+          nodir: true,
+        })
+      ).sort();
+  if (files.length === 0) {
+    return `No files found matching pattern: ${pattern}`;
+  }
+
+  // Unused imports are only known once the project is loaded
+  await waitForIdleIfRequested(waitForIdle);
+
+  const changed: FileImportChanges[] = [];
+  for (const file of files) {
+    const changes = await organizeFile(root, file, dryRun);
+    if (changes) {
+      changed.push(changes);
+    }
+  }
+
+  if (changed.length === 0) {
+    return `Imports are already organized in ${files.length} file(s)`;
+  }
+
+  const lines = [
+    dryRun
+      ? `Dry run: would organize imports in ${changed.length} of ${files.length} file(s). No files were modified.`
+      : `Organized imports in ${changed.length} of ${files.length} file(s)`,
+  ];
+  for (const file of changed) {
+    lines.push("", `${file.filePath} (${file.action})`);
+    lines.push(...file.removed.map((statement) => `  - ${statement}`));
+    lines.push(...file.added.map((statement) => `  + ${statement}`));
+    if (file.added.length === 0 && file.removed.length === 0) {
+      lines.push("  Imports reordered");
+    }
+  }
+  return lines.join("\n");
+}
+
+export const lspOrganizeImportsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_organize_imports",
+  description:
+    "Organize imports and remove unused ones in a file or all files matching a glob, using source.organizeImports code actions or unused-import quick fixes",
+  schema,
+  execute: async (args) => {
+    return handleOrganizeImports(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("extractImports", () => {
+    it("should join multi-line imports and use trees", () => {
+      const content = [
+        "use std::collections::HashMap;",
+        "pub(crate) use std::{",
+        "    fmt,",
+        "    io,",
+        "};",
+        "",
+        "fn main() {}",
+      ].join("\n");
+
+      expect(extractImports(content)).toEqual([
+        "use std::collections::HashMap;",
+        "pub(crate) use std::{ fmt, io, };",
+      ]);
+    });
+  });
+
+  describe("selectUnusedImportsFix", () => {
+    it("should prefer removing all unused imports over a single one", () => {
+      const fixes: CodeAction[] = [
+        { title: "Remove unused import", kind: "quickfix" },
+        { title: "Remove all unused imports", kind: "quickfix" },
+        { title: "Qualify `HashMap`", kind: "quickfix" },
+      ];
+
+      expect(selectUnusedImportsFix(fixes)?.title).toBe("Remove all unused imports");
+      expect(selectUnusedImportsFix(fixes.slice(0, 1))?.title).toBe("Remove unused import");
+      expect(selectUnusedImportsFix(fixes.slice(2))).toBeUndefined();
+    });
+  });
+
+  describe("diffImports", () => {
+    it("should report removed and added imports, ignoring formatting and order", () => {
+      const before = [
+        'import { b, a } from "./letters";',
+        'import { unused } from "./unused";',
+        'import {',
+        '  c,',
+        '} from "./c";',
+      ].join("\n");
+      const after = [
+        'import { c } from "./c";',
+        'import { a, b } from "./letters";',
+      ].join("\n");
+
+      expect(diffImports(before, after)).toEqual({
+        added: ['import { a, b } from "./letters";'],
+        removed: ['import { b, a } from "./letters";', 'import { unused } from "./unused";'],
+      });
+    });
+  });
+}
@@ -5,16 +5,18 @@ import { parseLineNumber } from "../../textUtils/parseLineNumber.ts";
 import { findSymbolInLine } from "../../textUtils/findSymbolInLine.ts";
 import { findTargetInFile } from "../../textUtils/findTargetInFile.ts";
 import type { ToolDef } from "../../mcp/_mcplib.ts";
-import { readFileSync, writeFileSync } from "fs";
+import { readFileSync } from "fs";
 import path from "path";
+import { pathToFileURL } from "url";
 import { 
   WorkspaceEdit,
-  TextDocumentEdit,
   TextEdit,
   Position,
 } from "vscode-languageserver-types";
 import { renameSymbolTool as tsRenameSymbolTool } from "../../ts/tools/tsRenameSymbol.ts";
 import { debug } from "../../mcp/_mcplib.ts";
+import { applyWorkspaceEdit } from "../applyWorkspaceEdit.ts";
+import { withLSPDocument } from "./lspCommon.ts";
 
 
 const schema = z.object({
@@ -28,12 +30,18 @@ const schema = z.object({
     .optional(),
   target: z.string().describe("Symbol to rename"),
   newName: z.string().describe("New name for the symbol"),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the rename as a unified diff without modifying any files"),
 });
 
 type RenameSymbolRequest = z.infer<typeof schema>;
 
 interface RenameSymbolSuccess {
   message: string;
+  // Unified diff of all changes (set for LSP renames)
+  diff?: string;
   changedFiles: {
     filePath: string;
     changes: {
@@ -47,84 +55,50 @@ interface RenameSymbolSuccess {
 
 
 /**
- * Helper to handle rename request when line is not provided
+ * Locate the symbol to rename, skipping matches in strings and comments.
+ * The document must already be open.
  */
-async function performRenameWithoutLine(
-  request: RenameSymbolRequest
-): Promise<Result<RenameSymbolSuccess, string>> {
-  try {
-    // Read file content
-    const absolutePath = path.resolve(request.root, request.filePath);
-    const fileContent = readFileSync(absolutePath, "utf-8");
-    const fileUri = `file://${absolutePath}`;
-    const lines = fileContent.split("\n");
+async function findRenamePosition(
+  request: RenameSymbolRequest,
+  fileUri: string,
+  fileContent: string
+): Promise<Result<Position, string>> {
+  const lines = fileContent.split("\n");
+  const tokens = (await getActiveClient().getSemanticTokens(fileUri)) ?? undefined;
 
-    // Find target text in file
-    const targetResult = findTargetInFile(lines, request.target);
+  if (request.line === undefined) {
+    const targetResult = findTargetInFile(lines, request.target, tokens);
     if ("error" in targetResult) {
       return err(`${targetResult.error} in ${request.filePath}`);
     }
+    return ok({
+      line: targetResult.lineIndex,
+      character: targetResult.characterIndex,
+    });
+  }
 
-    const { lineIndex: targetLine, characterIndex: symbolPosition } =
-      targetResult;
-
-    return performRenameAtPosition(
-      request,
-      fileUri,
-      fileContent,
-      targetLine,
-      symbolPosition
+  const lineResult = parseLineNumber(fileContent, request.line);
+  if ("error" in lineResult) {
+    return err(
+      `Line parameter "${String(request.line)}" not found in ${
+        request.filePath
+      }: ${lineResult.error}`
     );
-  } catch (error) {
-    return err(error instanceof Error ? error.message : String(error));
   }
-}
-
-/**
- * Handle rename request when line is provided
- */
-async function performRenameWithLine(
-  request: RenameSymbolRequest
-): Promise<Result<RenameSymbolSuccess, string>> {
-  try {
-    // Read file content
-    const absolutePath = path.resolve(request.root, request.filePath);
-    const fileContent = readFileSync(absolutePath, "utf-8");
-    const fileUri = `file://${absolutePath}`;
-    const lines = fileContent.split("\n");
-
-    // Parse line parameter
-    const lineResult = parseLineNumber(fileContent, request.line!);
-    if ("error" in lineResult) {
-      return err(
-        `Line parameter "${String(request.line)}" not found in ${
-          request.filePath
-        }: ${lineResult.error}`
-      );
-    }
 
-    const targetLine = lineResult.lineIndex;
-    const line = lines[targetLine];
-
-    // Find symbol position in line
-    const symbolResult = findSymbolInLine(line, request.target);
-    if ("error" in symbolResult) {
-      return err(
-        `Symbol "${request.target}" not found on line ${String(targetLine + 1)}: ${symbolResult.error}`
-      );
-    }
-    const symbolPosition = symbolResult.characterIndex;
-
-    return performRenameAtPosition(
-      request,
-      fileUri,
-      fileContent,
-      targetLine,
-      symbolPosition
+  const targetLine = lineResult.lineIndex;
+  const symbolResult = findSymbolInLine(
+    lines[targetLine],
+    request.target,
+    0,
+    tokens?.filter((token) => token.line === targetLine)
+  );
+  if ("error" in symbolResult) {
+    return err(
+      `Symbol "${request.target}" not found on line ${String(targetLine + 1)}: ${symbolResult.error}`
     );
-  } catch (error) {
-    return err(error instanceof Error ? error.message : String(error));
   }
+  return ok({ line: targetLine, character: symbolResult.characterIndex });
 }
 
 /**
@@ -133,17 +107,12 @@ async function performRenameWithLine(
 async function performRenameAtPosition(
   request: RenameSymbolRequest,
   fileUri: string,
-  fileContent: string,
   targetLine: number,
   symbolPosition: number
 ): Promise<Result<RenameSymbolSuccess, string>> {
   try {
     const client = getActiveClient();
 
-    // Open document in LSP
-    client.openDocument(fileUri, fileContent);
-    await new Promise<void>((resolve) => setTimeout(resolve, 1000));
-
     const position: Position = {
       line: targetLine,
       character: symbolPosition,
@@ -180,6 +149,9 @@ async function performRenameAtPosition(
           error.message?.includes("Unhandled method") ||
           error.message?.includes("Method not found")) {
         debug("LSP server doesn't support rename, falling back to TypeScript rename tool");
+        if (request.dryRun) {
+          return err("LSP rename is not supported by this server, and the TypeScript fallback cannot do a dry run");
+        }
         
         // Fall back to TypeScript rename tool
         try {
@@ -216,6 +188,9 @@ async function performRenameAtPosition(
     if (!workspaceEdit) {
       // LSP returned null, try TypeScript tool as fallback
       debug("LSP rename returned null, falling back to TypeScript rename tool");
+      if (request.dryRun) {
+        return err("LSP rename returned no edits, and the TypeScript fallback cannot do a dry run");
+      }
       
       try {
         await tsRenameSymbolTool.execute({
@@ -246,87 +221,34 @@ async function performRenameAtPosition(
     }
 
     // Apply changes and format result
-    const result = await applyWorkspaceEdit(request.root, workspaceEdit);
-    
-    // Close document
-    client.closeDocument(fileUri);
-
-    return ok(result);
+    return ok(applyRenameEdit(request, workspaceEdit));
   } catch (error) {
     return err(error instanceof Error ? error.message : String(error));
   }
 }
 
 /**
- * Apply workspace edit and return formatted result
+ * Apply (or preview in dry-run mode) the rename edit and return formatted result
  */
-async function applyWorkspaceEdit(
-  _root: string,
+function applyRenameEdit(
+  request: RenameSymbolRequest,
   workspaceEdit: WorkspaceEdit
-): Promise<RenameSymbolSuccess> {
-  const changedFiles: RenameSymbolSuccess["changedFiles"] = [];
-  const allFileContents = new Map<string, string[]>();
-
-  // Collect all file contents before applying changes
-  if (workspaceEdit.changes) {
-    for (const [uri, _edits] of Object.entries(workspaceEdit.changes)) {
-      const filePath = uri.replace("file://", "");
-      const content = readFileSync(filePath, "utf-8");
-      allFileContents.set(filePath, content.split("\n"));
-    }
-  }
-
-  if (workspaceEdit.documentChanges) {
-    for (const change of workspaceEdit.documentChanges) {
-      if (TextDocumentEdit.is(change)) {
-        const filePath = change.textDocument.uri.replace("file://", "");
-        if (!allFileContents.has(filePath)) {
-          const content = readFileSync(filePath, "utf-8");
-          allFileContents.set(filePath, content.split("\n"));
-        }
-      }
-    }
-  }
-
-  // Process changes from WorkspaceEdit.changes
-  if (workspaceEdit.changes) {
-    for (const [uri, edits] of Object.entries(workspaceEdit.changes)) {
-      const filePath = uri.replace("file://", "");
-      const lines = allFileContents.get(filePath)!;
-      const fileChanges = processTextEdits(filePath, lines, edits);
-      
-      if (fileChanges.changes.length > 0) {
-        changedFiles.push(fileChanges);
-        
-        // Apply edits to file
-        const newContent = applyTextEditsToContent(lines.join("\n"), edits);
-        writeFileSync(filePath, newContent, "utf-8");
-      }
-    }
-  }
+): RenameSymbolSuccess {
+  const { files, diff } = applyWorkspaceEdit(workspaceEdit, {
+    dryRun: request.dryRun,
+    root: request.root,
+  });
 
-  // Process changes from WorkspaceEdit.documentChanges
-  if (workspaceEdit.documentChanges) {
-    for (const change of workspaceEdit.documentChanges) {
-      if (TextDocumentEdit.is(change)) {
-        const filePath = change.textDocument.uri.replace("file://", "");
-        const lines = allFileContents.get(filePath)!;
-        const fileChanges = processTextEdits(filePath, lines, change.edits);
-        
-        if (fileChanges.changes.length > 0) {
-          // Check if we already processed this file
-          const existingFile = changedFiles.find(f => f.filePath === filePath);
-          if (existingFile) {
-            existingFile.changes.push(...fileChanges.changes);
-          } else {
-            changedFiles.push(fileChanges);
-          }
-          
-          // Apply edits to file
-          const newContent = applyTextEditsToContent(lines.join("\n"), change.edits);
-          writeFileSync(filePath, newContent, "utf-8");
-        }
-      }
+  // Summarize the applied edits against the original contents
+  const changedFiles: RenameSymbolSuccess["changedFiles"] = [];
+  for (const file of files) {
+    const fileChanges = processTextEdits(
+      file.oldFilePath ?? file.filePath,
+      file.oldContent.split("\n"),
+      file.edits
+    );
+    if (fileChanges.changes.length > 0) {
+      changedFiles.push(fileChanges);
     }
   }
 
@@ -336,8 +258,11 @@ async function applyWorkspaceEdit(
   );
 
   return {
-    message: `Successfully renamed symbol in ${changedFiles.length} file(s) with ${totalChanges} change(s)`,
+    message: request.dryRun
+      ? `Dry run: renaming would change ${changedFiles.length} file(s) with ${totalChanges} change(s). No files were modified.`
+      : `Successfully renamed symbol in ${changedFiles.length} file(s) with ${totalChanges} change(s)`,
     changedFiles,
+    diff,
   };
 }
 
@@ -384,59 +309,6 @@ function processTextEdits(
   };
 }
 
-/**
- * Apply text edits to content
- */
-function applyTextEditsToContent(content: string, edits: TextEdit[]): string {
-  // Sort edits by position (reverse order to avoid position shifts)
-  const sortedEdits = [...edits].sort((a, b) => {
-    if (a.range.start.line !== b.range.start.line) {
-      return b.range.start.line - a.range.start.line;
-    }
-    return b.range.start.character - a.range.start.character;
-  });
-
-  let result = content;
-  const lines = result.split("\n");
-
-  for (const edit of sortedEdits) {
-    const startLine = edit.range.start.line;
-    const startCol = edit.range.start.character;
-    const endLine = edit.range.end.line;
-    const endCol = edit.range.end.character;
-
-    if (startLine === endLine) {
-      // Single line edit
-      lines[startLine] =
-        lines[startLine].substring(0, startCol) +
-        edit.newText +
-        lines[startLine].substring(endCol);
-    } else {
-      // Multi-line edit
-      const newLines = edit.newText.split("\n");
-      const before = lines[startLine].substring(0, startCol);
-      const after = lines[endLine].substring(endCol);
-      
-      // Remove old lines
-      lines.splice(startLine, endLine - startLine + 1);
-      
-      // Insert new lines
-      if (newLines.length === 1) {
-        lines.splice(startLine, 0, before + newLines[0] + after);
-      } else {
-        const insertLines = [
-          before + newLines[0],
-          ...newLines.slice(1, -1),
-          newLines[newLines.length - 1] + after,
-        ];
-        lines.splice(startLine, 0, ...insertLines);
-      }
-    }
-  }
-
-  return lines.join("\n");
-}
-
 /**
  * Handle rename symbol request
  */
@@ -444,11 +316,23 @@ async function handleRenameSymbol(
   request: RenameSymbolRequest
 ): Promise<Result<RenameSymbolSuccess, string>> {
   try {
-    if (request.line !== undefined) {
-      return performRenameWithLine(request);
-    } else {
-      return performRenameWithoutLine(request);
-    }
+    const absolutePath = path.resolve(request.root, request.filePath);
+    const fileContent = readFileSync(absolutePath, "utf-8");
+    const fileUri = pathToFileURL(absolutePath).toString();
+
+    // The document stays open until the rename is applied, and is closed on every path
+    return await withLSPDocument(fileUri, fileContent, async () => {
+      const position = await findRenamePosition(request, fileUri, fileContent);
+      if (position.isErr()) {
+        return err(position.error);
+      }
+      return performRenameAtPosition(
+        request,
+        fileUri,
+        position.value.line,
+        position.value.character
+      );
+    });
   } catch (error) {
     return err(error instanceof Error ? error.message : String(error));
   }
@@ -466,7 +350,7 @@ export const lspRenameSymbolTool: ToolDef<typeof schema> = {
     }
 
     // Format output
-    const { message, changedFiles } = result.value;
+    const { message, changedFiles, diff } = result.value;
     const output = [message, "", "Changes:"];
 
     for (const file of changedFiles) {
@@ -480,6 +364,10 @@ export const lspRenameSymbolTool: ToolDef<typeof schema> = {
       }
     }
 
+    if (args.dryRun && diff) {
+      output.push("", "Diff:", diff);
+    }
+
     return output.join("\n");
   },
 };
\ No newline at end of file
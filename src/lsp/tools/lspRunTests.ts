@@ -0,0 +1,360 @@
+import { z } from "zod";
+import path from "path";
+import { spawn } from "child_process";
+import { readFileSync } from "fs";
+import { pathToFileURL } from "url";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import { resolveLineParameter } from "../../textUtils/resolveLineParameter.ts";
+import type { TestCaseResult } from "../../common/testResults.ts";
+import { selectByIndexOrLabel } from "../../common/selection.ts";
+import { waitForIdleIfRequested, waitForIdleShape } from "./lspCommon.ts";
+import {
+  getRunnableLocation,
+  getRustRunnables,
+  parseLibtestOutput,
+  runnableToCommand,
+} from "../../rust/runnables.ts";
+import {
+  buildTestCommand,
+  detectTestFramework,
+  discoverTestBlocks,
+  parseJestJsonReport,
+} from "../../ts/testRunner.ts";
+
+const DEFAULT_TEST_TIMEOUT = 300000;
+
+const schema = z.object({
+  root: z.string().describe("Root directory for resolving relative paths"),
+  filePath: z.string().describe("File containing the tests (relative to root)"),
+  line: z
+    .union([z.number(), z.string()])
+    .describe("Line number (1-based) or string to match; only runnables enclosing it are listed")
+    .optional(),
+  index: z
+    .number()
+    .describe("Number of the runnable to run, as listed (1-based)")
+    .optional(),
+  label: z
+    .string()
+    .describe("Label of the runnable to run (exact match, or a unique case-insensitive substring)")
+    .optional(),
+  timeout: z
+    .number()
+    .describe("Maximum run time in milliseconds (default: 300000)")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+type RunTestsRequest = z.infer<typeof schema>;
+
+interface TestRunnable {
+  label: string;
+  location?: { filePath: string; line: number };
+  command: string;
+  args: string[];
+  cwd: string;
+  env?: Record<string, string>;
+  parse: (stdout: string) => TestCaseResult[];
+}
+
+interface ProcessResult {
+  stdout: string;
+  stderr: string;
+  exitCode: number | null;
+  timedOut: boolean;
+  durationMs: number;
+}
+
+/**
+ * List runnables via rust-analyzer for Rust files, and vitest/jest discovery otherwise
+ */
+async function getTestRunnables(
+  request: RunTestsRequest,
+  absolutePath: string,
+  content: string,
+  lineIndex?: number
+): Promise<TestRunnable[]> {
+  if (absolutePath.endsWith(".rs")) {
+    const client = getLSPClient();
+    if (!client) {
+      throw new Error("LSP client not initialized");
+    }
+    const fileUri = pathToFileURL(absolutePath).toString();
+    // Runnables are only complete once the crate graph is indexed
+    await waitForIdleIfRequested(request.waitForIdle);
+    client.openDocument(fileUri, content);
+    try {
+      const runnables = await getRustRunnables(
+        client,
+        fileUri,
+        lineIndex !== undefined ? { line: lineIndex, character: 0 } : undefined
+      );
+      return runnables.map((runnable) => {
+        const command = runnableToCommand(runnable, request.root);
+        return {
+          label: runnable.label,
+          location: getRunnableLocation(runnable),
+          command: command.command,
+          args: command.args,
+          cwd: command.cwd,
+          env: command.env,
+          parse: (stdout) => parseLibtestOutput(stdout, command.sourceRoot),
+        };
+      });
+    } finally {
+      client.closeDocument(fileUri);
+    }
+  }
+
+  const detected = detectTestFramework(absolutePath);
+  if (!detected) {
+    throw new Error(
+      `No vitest or jest dependency found in a package.json above ${request.filePath}`
+    );
+  }
+  const { framework, packageDir } = detected;
+
+  const blocks = discoverTestBlocks(content).filter(
+    (block) =>
+      lineIndex === undefined ||
+      (block.startLine <= lineIndex + 1 && lineIndex + 1 <= block.endLine)
+  );
+  const toRunnable = (
+    label: string,
+    block?: (typeof blocks)[number]
+  ): TestRunnable => ({
+    label,
+    location: block && { filePath: absolutePath, line: block.startLine },
+    ...buildTestCommand(framework, packageDir, absolutePath, block),
+    parse: parseJestJsonReport,
+  });
+
+  return [
+    toRunnable(`${framework} ${path.relative(packageDir, absolutePath)}`),
+    ...blocks.map((block) => toRunnable(`${block.kind} ${block.name}`, block)),
+  ];
+}
+
+function runProcess(runnable: TestRunnable, timeout: number): Promise<ProcessResult> {
+  return new Promise<ProcessResult>((resolve, reject) => {
+    const startedAt = Date.now();
+    const proc = spawn(runnable.command, runnable.args, {
+      cwd: runnable.cwd,
+      env: { ...process.env, ...runnable.env },
+      stdio: ["ignore", "pipe", "pipe"],
+    });
+
+    let stdout = "";
+    let stderr = "";
+    let timedOut = false;
+    const timer = setTimeout(() => {
+      timedOut = true;
+      proc.kill();
+    }, timeout);
+
+    proc.stdout.on("data", (data: Buffer) => {
+      stdout += data.toString();
+    });
+    proc.stderr.on("data", (data: Buffer) => {
+      stderr += data.toString();
+    });
+    proc.on("error", (error) => {
+      clearTimeout(timer);
+      reject(error);
+    });
+    proc.on("close", (code) => {
+      clearTimeout(timer);
+      resolve({
+        stdout,
+        stderr,
+        exitCode: code,
+        timedOut,
+        durationMs: Date.now() - startedAt,
+      });
+    });
+  });
+}
+
+function formatCommand(runnable: TestRunnable): string {
+  return [runnable.command, ...runnable.args]
+    .map((arg) => (/[\s"'$]/.test(arg) ? JSON.stringify(arg) : arg))
+    .join(" ");
+}
+
+function formatLocation(root: string, location: { filePath: string; line: number }): string {
+  return `${path.relative(root, location.filePath)}:${location.line}`;
+}
+
+function formatRunnables(
+  root: string,
+  filePath: string,
+  runnables: TestRunnable[]
+): string {
+  const lines = [`Runnables for ${filePath}:`, ""];
+  runnables.forEach((runnable, i) => {
+    const location = runnable.location
+      ? ` (${formatLocation(root, runnable.location)})`
+      : "";
+    lines.push(`${i + 1}. ${runnable.label}${location}`);
+    lines.push(`   ${formatCommand(runnable)}`);
+  });
+  lines.push("", "Run one by passing its index or label.");
+  return lines.join("\n");
+}
+
+/**
+ * Format a test run as a pass/fail summary followed by per-test results
+ */
+function formatTestRun(
+  root: string,
+  runnable: TestRunnable,
+  result: ProcessResult,
+  tests: TestCaseResult[]
+): string {
+  const count = (status: TestCaseResult["status"]) =>
+    tests.filter((test) => test.status === status).length;
+  const passed = count("passed");
+  const failed = count("failed");
+  const ignored = count("ignored");
+  const seconds = (result.durationMs / 1000).toFixed(1);
+
+  const status = result.timedOut
+    ? "TIMED OUT"
+    : failed > 0 || result.exitCode !== 0
+      ? "FAILED"
+      : "PASSED";
+
+  const lines = [
+    `Command: ${formatCommand(runnable)}`,
+    `Result: ${status} - ${passed} passed, ${failed} failed, ${ignored} ignored (${seconds}s)`,
+  ];
+
+  if (tests.length > 0) {
+    lines.push("");
+  }
+  for (const test of tests) {
+    const label = test.status === "passed" ? "PASS" : test.status === "failed" ? "FAIL" : "SKIP";
+    lines.push(`${label} ${test.name}`);
+    if (test.status !== "failed") continue;
+    if (test.location) {
+      lines.push(`  at ${formatLocation(root, test.location)}`);
+    }
+    if (test.message) {
+      lines.push(...test.message.split("\n").map((line) => `  ${line}`));
+    }
+  }
+
+  // Build errors or crashes leave no per-test results, so show the raw output
+  if (tests.length === 0 && status !== "PASSED") {
+    const output = `${result.stderr}\n${result.stdout}`.trim().split("\n");
+    lines.push("", "Output:", ...output.slice(-50));
+  }
+
+  return lines.join("\n");
+}
+
+async function handleRunTests(request: RunTestsRequest): Promise<string> {
+  const absolutePath = path.resolve(request.root, request.filePath);
+  const content = readFileSync(absolutePath, "utf-8");
+
+  let lineIndex: number | undefined;
+  if (request.line !== undefined) {
+    const resolved = resolveLineParameter(content, request.line);
+    if (!resolved.success) {
+      throw new Error(resolved.error);
+    }
+    lineIndex = resolved.lineIndex;
+  }
+
+  const runnables = await getTestRunnables(request, absolutePath, content, lineIndex);
+  if (runnables.length === 0) {
+    return `No runnables found in ${request.filePath}${lineIndex !== undefined ? `:${lineIndex + 1}` : ""}`;
+  }
+
+  if (request.index === undefined && request.label === undefined) {
+    return formatRunnables(request.root, request.filePath, runnables);
+  }
+
+  const runnable = selectByIndexOrLabel(
+    runnables,
+    (runnable) => runnable.label,
+    { singular: "runnable", plural: "runnables", labelName: "label" },
+    request.index,
+    request.label
+  );
+  const result = await runProcess(runnable, request.timeout ?? DEFAULT_TEST_TIMEOUT);
+  return formatTestRun(request.root, runnable, result, runnable.parse(result.stdout));
+}
+
+export const lspRunTestsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_run_tests",
+  description:
+    "List runnable tests in a file (rust-analyzer runnables for Rust, vitest/jest for TypeScript) and run one, returning pass/fail results with failure locations",
+  schema,
+  execute: async (args) => {
+    return handleRunTests(args);
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("formatTestRun", () => {
+    const runnable: TestRunnable = {
+      label: "test-mod tests",
+      command: "cargo",
+      args: ["test", "--package", "rust-project", "--lib", "--", "tests"],
+      cwd: "/work",
+      parse: () => [],
+    };
+
+    it("should summarize results and map failures to file:line", () => {
+      const output = formatTestRun(
+        "/work",
+        runnable,
+        { stdout: "", stderr: "", exitCode: 101, timedOut: false, durationMs: 1234 },
+        [
+          { name: "tests::test_greet", status: "passed" },
+          {
+            name: "tests::test_calculator",
+            status: "failed",
+            message: "assertion `left == right` failed\n  left: 3.0",
+            location: { filePath: "/work/src/lib.rs", line: 43 },
+          },
+        ]
+      );
+
+      expect(output).toBe(
+        [
+          "Command: cargo test --package rust-project --lib -- tests",
+          "Result: FAILED - 1 passed, 1 failed, 0 ignored (1.2s)",
+          "",
+          "PASS tests::test_greet",
+          "FAIL tests::test_calculator",
+          "  at src/lib.rs:43",
+          "  assertion `left == right` failed",
+          "    left: 3.0",
+        ].join("\n")
+      );
+    });
+
+    it("should show raw output when no tests were reported", () => {
+      const output = formatTestRun(
+        "/work",
+        runnable,
+        {
+          stdout: "",
+          stderr: "error[E0308]: mismatched types",
+          exitCode: 101,
+          timedOut: false,
+          durationMs: 500,
+        },
+        []
+      );
+
+      expect(output).toContain("Result: FAILED - 0 passed, 0 failed, 0 ignored");
+      expect(output).toContain("Output:\nerror[E0308]: mismatched types");
+    });
+  });
+}
@@ -0,0 +1,122 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../lspClient.ts";
+import type { ServerStatus, WorkDoneProgress } from "../lspTypes.ts";
+
+const schema = z.object({
+  waitForIdle: z
+    .boolean()
+    .optional()
+    .describe("Wait until in-flight work finishes before reporting"),
+  timeout: z
+    .number()
+    .optional()
+    .describe("Maximum time in milliseconds to wait when waitForIdle is set"),
+});
+
+interface ServerStatusReport {
+  idle: boolean;
+  workspaceFolders: string[];
+  serverStatus: ServerStatus | null;
+  progress: WorkDoneProgress[];
+}
+
+/**
+ * Format the server status with in-flight progress and elapsed times
+ */
+function formatServerStatus(report: ServerStatusReport, now: number): string {
+  const lines = [`Status: ${report.idle ? "idle" : "busy"}`];
+
+  if (report.serverStatus) {
+    const { health, quiescent, message } = report.serverStatus;
+    lines.push(
+      `Server health: ${health}${quiescent ? "" : " (indexing)"}${message ? ` - ${message}` : ""}`
+    );
+  }
+
+  lines.push("", "Workspace folders:");
+  for (const folder of report.workspaceFolders) {
+    lines.push(`  ${folder}`);
+  }
+
+  if (report.progress.length > 0) {
+    lines.push("", "In progress:");
+    for (const progress of report.progress) {
+      const percentage =
+        progress.percentage !== undefined ? ` ${progress.percentage}%` : "";
+      const message = progress.message ? ` - ${progress.message}` : "";
+      const elapsed = ((now - progress.startedAt) / 1000).toFixed(1);
+      lines.push(`  ${progress.title}${percentage}${message} (${elapsed}s)`);
+    }
+  }
+
+  return lines.join("\n");
+}
+
+export const lspServerStatusTool: ToolDef<typeof schema> = {
+  name: "lsmcp_server_status",
+  description:
+    "Show whether the language server is ready, with in-flight progress such as indexing",
+  schema,
+  execute: async ({ waitForIdle, timeout }) => {
+    const client = getLSPClient();
+    if (!client) {
+      throw new Error("LSP client not initialized");
+    }
+
+    if (waitForIdle) {
+      await client.waitForIdle({ timeout });
+    }
+
+    return formatServerStatus(
+      {
+        idle: client.isIdle(),
+        workspaceFolders: client.getWorkspaceFolders().map((f) => f.uri),
+        serverStatus: client.getServerStatus(),
+        progress: client.getProgress(),
+      },
+      Date.now()
+    );
+  },
+};
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("formatServerStatus", () => {
+    it("should show in-flight progress with elapsed time", () => {
+      const output = formatServerStatus(
+        {
+          idle: false,
+          workspaceFolders: ["file:///work/rust-project"],
+          serverStatus: { health: "ok", quiescent: false },
+          progress: [
+            {
+              token: "rustAnalyzer/Indexing",
+              title: "Indexing",
+              message: "12/48 (core)",
+              percentage: 25,
+              startedAt: 1000,
+            },
+          ],
+        },
+        3500
+      );
+
+      expect(output).toContain("Status: busy");
+      expect(output).toContain("Server health: ok (indexing)");
+      expect(output).toContain("file:///work/rust-project");
+      expect(output).toContain("Indexing 25% - 12/48 (core) (2.5s)");
+    });
+
+    it("should report an idle server", () => {
+      const output = formatServerStatus(
+        { idle: true, workspaceFolders: [], serverStatus: null, progress: [] },
+        0
+      );
+
+      expect(output).toContain("Status: idle");
+      expect(output).not.toContain("In progress");
+    });
+  });
+}
@@ -8,6 +8,7 @@
  */
 
 import { parseArgs } from "node:util";
+import type { ZodType } from "zod";
 import {
   BaseMcpServer,
   StdioServerTransport,
@@ -18,6 +19,7 @@ import { lspGetHoverTool } from "../lsp/tools/lspGetHover.ts";
 import { lspFindReferencesTool } from "../lsp/tools/lspFindReferences.ts";
 import { lspGetDefinitionsTool } from "../lsp/tools/lspGetDefinitions.ts";
 import { lspGetDiagnosticsTool } from "../lsp/tools/lspGetDiagnostics.ts";
+import { lspGetWorkspaceDiagnosticsTool } from "../lsp/tools/lspGetWorkspaceDiagnostics.ts";
 import { lspRenameSymbolTool } from "../lsp/tools/lspRenameSymbol.ts";
 import { lspGetDocumentSymbolsTool } from "../lsp/tools/lspGetDocumentSymbols.ts";
 import { lspGetWorkspaceSymbolsTool } from "../lsp/tools/lspGetWorkspaceSymbols.ts";
@@ -25,10 +27,28 @@ import { lspGetCompletionTool } from "../lsp/tools/lspGetCompletion.ts";
 import { lspGetSignatureHelpTool } from "../lsp/tools/lspGetSignatureHelp.ts";
 import { lspFormatDocumentTool } from "../lsp/tools/lspFormatDocument.ts";
 import { lspGetCodeActionsTool } from "../lsp/tools/lspGetCodeActions.ts";
+import { lspApplyCodeActionTool } from "../lsp/tools/lspApplyCodeAction.ts";
+import { lspServerStatusTool } from "../lsp/tools/lspServerStatus.ts";
+import { lspRunTestsTool } from "../lsp/tools/lspRunTests.ts";
+import { lspGetCallHierarchyTool } from "../lsp/tools/lspGetCallHierarchy.ts";
+import { lspFindImplementationsTool } from "../lsp/tools/lspFindImplementations.ts";
+import { lspGetTypeDefinitionTool } from "../lsp/tools/lspGetTypeDefinition.ts";
+import { lspGetTypeHierarchyTool } from "../lsp/tools/lspGetTypeHierarchy.ts";
+import { lspGetInlayHintsTool } from "../lsp/tools/lspGetInlayHints.ts";
+import { lspGetEnclosingRangeTool } from "../lsp/tools/lspGetEnclosingRange.ts";
+import { lspMoveFileTool } from "../lsp/tools/lspMoveFile.ts";
+import { lspExtractFunctionTool } from "../lsp/tools/lspExtractFunction.ts";
+import { lspExtractVariableTool } from "../lsp/tools/lspExtractVariable.ts";
+import { lspInlineSymbolTool } from "../lsp/tools/lspInlineSymbol.ts";
+import { lspDeleteSymbolTool } from "../lsp/tools/lspDeleteSymbol.ts";
+import { lspOrganizeImportsTool } from "../lsp/tools/lspOrganizeImports.ts";
+import { rustAnalyzerTools } from "../rust/tools/index.ts";
+import { isRustAnalyzerCommand } from "../rust/rustAnalyzer.ts";
 import { listToolsTool } from "./tools/listTools.ts";
 import { spawn } from "child_process";
-import { initialize as initializeLSPClient } from "../lsp/lspClient.ts";
+import { initialize as initializeLSPClient, getLSPClient } from "../lsp/lspClient.ts";
 import { getLanguageFromLSPCommand } from "./utils/languageSupport.ts";
+import { findWorkspaceFolder, findWorkspaceRoot } from "./utils/workspaceRoot.ts";
 import { formatError, ErrorContext } from "./utils/errorHandler.ts";
 
 // Define LSP-only tools
@@ -38,6 +58,7 @@ const tools: ToolDef<any>[] = [
   lspFindReferencesTool,
   lspGetDefinitionsTool,
   lspGetDiagnosticsTool,
+  lspGetWorkspaceDiagnosticsTool,
   lspRenameSymbolTool,
   lspGetDocumentSymbolsTool,
   lspGetWorkspaceSymbolsTool,
@@ -45,8 +66,72 @@ const tools: ToolDef<any>[] = [
   lspGetSignatureHelpTool,
   lspFormatDocumentTool,
   lspGetCodeActionsTool,
+  lspApplyCodeActionTool,
+  lspServerStatusTool,
+  lspRunTestsTool,
+  lspGetCallHierarchyTool,
+  lspFindImplementationsTool,
+  lspGetTypeDefinitionTool,
+  lspGetTypeHierarchyTool,
+  lspGetInlayHintsTool,
+  lspGetEnclosingRangeTool,
+  lspMoveFileTool,
+  lspExtractFunctionTool,
+  lspExtractVariableTool,
+  lspInlineSymbolTool,
+  lspDeleteSymbolTool,
+  lspOrganizeImportsTool,
 ];
 
+/**
+ * Parse a JSON-valued environment variable, or return undefined if unset
+ */
+function parseJsonEnv(name: string, language: string): unknown {
+  const value = process.env[name];
+  if (!value) {
+    return undefined;
+  }
+  try {
+    return JSON.parse(value);
+  } catch (error) {
+    const context: ErrorContext = {
+      operation: "LSP server configuration",
+      language,
+      details: { [name]: value }
+    };
+    throw new Error(formatError(error, context));
+  }
+}
+
+/**
+ * Wrap a tool so that the project of its `root` (or of its `filePath`, when the
+ * root spans several projects) is registered with the LSP server as a workspace
+ * folder before the tool runs
+ */
+function withWorkspaceFolder<S extends ZodType>(
+  tool: ToolDef<S>,
+  language: string
+): ToolDef<S> {
+  return {
+    ...tool,
+    execute: (args) => {
+      const { root, filePath } = args as { root?: unknown; filePath?: unknown };
+      const client = getLSPClient();
+      if (client && typeof root === "string" && root) {
+        const folder = findWorkspaceFolder(
+          root,
+          typeof filePath === "string" ? filePath : undefined,
+          language
+        );
+        if (client.addWorkspaceFolder(folder)) {
+          debug(`[lsp] Added workspace folder: ${folder}`);
+        }
+      }
+      return tool.execute(args);
+    },
+  };
+}
+
 async function main() {
   try {
     // Parse command line arguments
@@ -79,6 +164,21 @@ async function main() {
 
     const detectedLanguage = getLanguageFromLSPCommand(lspCommand);
 
+    // The LSP server is rooted at the enclosing workspace (e.g. the Cargo
+    // `[workspace]` above a member crate) unless LSP_ROOT overrides it
+    const detectedRoot = findWorkspaceRoot(projectRoot, detectedLanguage);
+    const lspRoot = process.env.LSP_ROOT || detectedRoot.root;
+    const lspRootSource = process.env.LSP_ROOT
+      ? "LSP_ROOT"
+      : detectedRoot.marker ?? "current directory";
+
+    // Language presets (e.g. `lsmcp -l rust`) may pass tuned initialization
+    // options and the settings returned for workspace/configuration
+    const initializationOptions = parseJsonEnv("LSP_INIT_OPTIONS", detectedLanguage);
+    const settings = parseJsonEnv("LSP_SETTINGS", detectedLanguage) as
+      | Record<string, unknown>
+      | undefined;
+
     // Start MCP server
     const server = new BaseMcpServer({
       name: "generic-lsp",
@@ -90,7 +190,13 @@ async function main() {
     });
     
     server.setDefaultRoot(projectRoot);
-    server.registerTools(tools);
+    // rust-analyzer extension requests are only understood by rust-analyzer
+    const languageTools = isRustAnalyzerCommand(lspCommand) ? rustAnalyzerTools : [];
+    server.registerTools(
+      [...tools, ...languageTools].map((tool) =>
+        withWorkspaceFolder(tool, detectedLanguage)
+      )
+    );
 
     // Initialize LSP client
     const parts = lspCommand.split(" ");
@@ -100,7 +206,7 @@ async function main() {
     let lspProcess;
     try {
       lspProcess = spawn(command, args, {
-        cwd: projectRoot,
+        cwd: lspRoot,
         stdio: ["pipe", "pipe", "pipe"],
       });
     } catch (error) {
@@ -113,7 +219,13 @@ async function main() {
     }
     
     try {
-      await initializeLSPClient(projectRoot, lspProcess, detectedLanguage.toLowerCase());
+      await initializeLSPClient(
+        lspRoot,
+        lspProcess,
+        detectedLanguage.toLowerCase(),
+        initializationOptions,
+        settings
+      );
       debug(`[lsp] Initialized LSP client: ${lspCommand}`);
     } catch (error) {
       const context: ErrorContext = {
@@ -130,6 +242,7 @@ async function main() {
     
     debug(`Generic LSP MCP Server running on stdio`);
     debug(`Project root: ${projectRoot}`);
+    debug(`LSP root: ${lspRoot} (from ${lspRootSource})`);
     debug(`LSP command: ${lspCommand}`);
     debug(`Language: ${detectedLanguage}`);
   } catch (error) {
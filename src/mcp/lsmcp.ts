@@ -3,7 +3,7 @@
  * lsmcp - Language Service MCP
  * 
  * Main entry point for the lsmcp tool that provides MCP integration
- * for TypeScript/JavaScript (built-in) or any LSP server (via --bin).
+ * for TypeScript/JavaScript and Rust (built-in) or any LSP server (via --bin).
  */
 
 import { parseArgs } from "node:util";
@@ -14,6 +14,13 @@ import { fileURLToPath } from "url";
 import { spawn } from "child_process";
 import { debug } from "./_mcplib.ts";
 import { formatError, ErrorContext } from "./utils/errorHandler.ts";
+import {
+  findRustAnalyzer,
+  RUST_ANALYZER_INITIALIZATION_OPTIONS,
+} from "../rust/rustAnalyzer.ts";
+
+// Languages with a built-in preset for --language
+const SUPPORTED_LANGUAGES = ["typescript", "javascript", "rust"];
 
 // Parse command line arguments
 const { values, positionals } = parseArgs({
@@ -61,35 +68,65 @@ Options:
 
 Examples:
   lsmcp -l typescript          Use TypeScript MCP server
-  lsmcp -l rust                Use Rust MCP server
+  lsmcp -l rust                Use Rust MCP server (rust-analyzer)
   lsmcp --bin "deno lsp"       Use custom LSP server
   lsmcp --include "src/**/*.ts" -l typescript  Get diagnostics for TypeScript files
 
 Supported Languages:
   - TypeScript/JavaScript (built-in support)
+  - Rust (built-in preset using rust-analyzer)
   - Any language via LSP server with --bin option
 
 Environment Variables:
   FORCE_LANGUAGE        Force a specific language (same as -l)
+  RUST_ANALYZER_PATH    Path to rust-analyzer (default: rustup toolchain or PATH)
 `);
 }
 
+/**
+ * Build the environment for the Rust preset. The Cargo workspace root is
+ * detected by generic-lsp-mcp.
+ */
+function getRustServerEnv(): Record<string, string> {
+  const rustAnalyzer = findRustAnalyzer();
+  if (!rustAnalyzer) {
+    const context: ErrorContext = {
+      operation: "LSP server startup",
+      language: "rust"
+    };
+    console.error(formatError(new Error("command not found: rust-analyzer"), context));
+    process.exit(1);
+  }
+
+  debug(`Using rust-analyzer: ${rustAnalyzer}`);
+
+  return {
+    LSP_COMMAND: rustAnalyzer,
+    LSP_INIT_OPTIONS: JSON.stringify(RUST_ANALYZER_INITIALIZATION_OPTIONS),
+    // rust-analyzer re-reads its settings via workspace/configuration
+    LSP_SETTINGS: JSON.stringify({
+      "rust-analyzer": RUST_ANALYZER_INITIALIZATION_OPTIONS,
+    }),
+  };
+}
+
 async function runLanguageServer(language: string, args: string[] = [], customEnv?: Record<string, string | undefined>) {
-  // Only TypeScript MCP server is available now
-  if (language !== "typescript" && language !== "javascript") {
+  if (!SUPPORTED_LANGUAGES.includes(language)) {
     console.error(`Error: Language '${language}' is not supported in this build.`);
-    console.error("Only TypeScript/JavaScript is currently supported.");
+    console.error(`Supported languages: ${SUPPORTED_LANGUAGES.join(", ")}`);
     console.error("Use --bin option to use custom LSP servers for other languages.");
     process.exit(1);
   }
 
-  // Get the path to the TypeScript server
+  // Rust runs on the generic LSP server, TypeScript/JavaScript on the TypeScript server
+  const isRust = language === "rust";
   const __filename = fileURLToPath(import.meta.url);
   const __dirname = dirname(__filename);
-  const serverPath = join(__dirname, "typescript-mcp.js");
+  const serverPath = join(__dirname, isRust ? "generic-lsp-mcp.js" : "typescript-mcp.js");
 
   // Merge environment variables
-  const env = customEnv ? { ...process.env, ...customEnv } : process.env;
+  const languageEnv = isRust ? getRustServerEnv() : {};
+  const env = { ...process.env, ...languageEnv, ...customEnv };
 
   if (!existsSync(serverPath)) {
     const context: ErrorContext = {
@@ -136,8 +173,8 @@ async function main() {
     console.log("Supported languages:");
     console.log("  typescript - TypeScript files (.ts, .tsx)");
     console.log("  javascript - JavaScript files (.js, .jsx)");
+    console.log("  rust       - Rust files (.rs) via rust-analyzer");
     console.log("\nFor other languages, use --bin with an LSP server:");
-    console.log("  --bin \"rust-analyzer\" for Rust");
     console.log("  --bin \"pylsp\" for Python");
     console.log("  --bin \"gopls\" for Go");
     process.exit(0);
@@ -286,8 +323,8 @@ async function main() {
 
   if (language) {
     // Validate language
-    if (language !== "typescript" && language !== "javascript") {
-      console.error(`Error: Only TypeScript/JavaScript are supported with --language`);
+    if (!SUPPORTED_LANGUAGES.includes(language)) {
+      console.error(`Error: Only ${SUPPORTED_LANGUAGES.join(", ")} are supported with --language`);
       console.error("For other languages, use --bin option with an LSP server");
       process.exit(1);
     }
@@ -120,6 +120,12 @@ const TOOLS_REGISTRY: ToolInfo[] = [
     category: "lsp",
     requiresLSP: true,
   },
+  {
+    name: "lsp_get_workspace_diagnostics",
+    description: "Get diagnostics for all files matching a glob pattern using LSP, grouped by file",
+    category: "lsp",
+    requiresLSP: true,
+  },
   {
     name: "lsp_rename_symbol",
     description: "Rename a symbol across the codebase using Language Server Protocol",
@@ -127,8 +133,8 @@ const TOOLS_REGISTRY: ToolInfo[] = [
     requiresLSP: true,
   },
   {
-    name: "lsp_delete_symbol",
-    description: "Delete a symbol and optionally all its references using LSP",
+    name: "lsp_delete_unused_symbol",
+    description: "Delete an unreferenced symbol with its doc comments and attributes using LSP",
     category: "lsp",
     requiresLSP: true,
   },
@@ -162,12 +168,144 @@ const TOOLS_REGISTRY: ToolInfo[] = [
     category: "lsp",
     requiresLSP: true,
   },
+  {
+    name: "lsp_apply_code_action",
+    description: "Apply a code action by index or title and show the resulting diff using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
   {
     name: "lsp_format_document",
     description: "Format an entire document using the language server's formatting provider",
     category: "lsp",
     requiresLSP: true,
   },
+  {
+    name: "lsp_server_status",
+    description: "Show language server readiness and in-flight progress such as indexing",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_run_tests",
+    description: "List and run tests in a file with pass/fail results mapped to file:line",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_get_call_hierarchy",
+    description: "Show callers and callees of a function as a depth-limited tree",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_find_implementations",
+    description: "Find implementations of a trait, interface or abstract method",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_get_type_definition",
+    description: "Go to the definition of a symbol's type",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_get_type_hierarchy",
+    description: "Show supertypes and subtypes of a type as a tree",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_get_inlay_hints",
+    description: "Show a file range annotated with inferred types and parameter names",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_get_enclosing_range",
+    description: "Get the source of the function, impl or class enclosing a line",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_move_file",
+    description: "Move a file and update references to it using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_extract_function",
+    description: "Extract a range of lines into a new function using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_extract_variable",
+    description: "Extract an expression into a new variable using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_inline_symbol",
+    description: "Inline a variable or function at its use sites using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_organize_imports",
+    description: "Organize imports and remove unused ones using LSP",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_expand_macro",
+    description: "Expand the Rust macro call at a position (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_syntax_tree",
+    description: "Show the syntax tree of a Rust file or line range (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_view_hir",
+    description: "Show the HIR of the Rust function at a position (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_view_item_tree",
+    description: "Show the item tree of a Rust file (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_parent_module",
+    description: "Go to the parent module declaration of a Rust file (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_open_cargo_toml",
+    description: "Find the Cargo.toml of a Rust file's crate (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_rust_related_tests",
+    description: "Find the tests related to a Rust item (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
+  {
+    name: "lsp_structural_replace",
+    description: "Structural search and replace with SSR rules (rust-analyzer only)",
+    category: "lsp",
+    requiresLSP: true,
+  },
 ];
 
 function formatToolsList(tools: ToolInfo[], category: string): string {
@@ -253,6 +391,12 @@ export function createLanguageListTool(
       category: "lsp",
       requiresLSP: true,
     },
+    {
+      name: `${language}_get_workspace_diagnostics`,
+      description: `Get ${displayName} diagnostics for all files matching a glob pattern using LSP`,
+      category: "lsp",
+      requiresLSP: true,
+    },
     {
       name: `${language}_rename_symbol`,
       description: `Rename a ${displayName} symbol across the codebase using Language Server Protocol`,
@@ -260,8 +404,8 @@ export function createLanguageListTool(
       requiresLSP: true,
     },
     {
-      name: `${language}_delete_symbol`,
-      description: `Delete a ${displayName} symbol and optionally all its references using LSP`,
+      name: `${language}_delete_unused_symbol`,
+      description: `Delete an unreferenced ${displayName} symbol with its doc comments and attributes using LSP`,
       category: "lsp",
       requiresLSP: true,
     },
@@ -295,14 +439,152 @@ export function createLanguageListTool(
       category: "lsp",
       requiresLSP: true,
     },
+    {
+      name: `${language}_apply_code_action`,
+      description: `Apply a code action to ${displayName} code using LSP`,
+      category: "lsp",
+      requiresLSP: true,
+    },
     {
       name: `${language}_format_document`,
       description: `Format a ${displayName} document using the language server's formatting provider`,
       category: "lsp",
       requiresLSP: true,
     },
+    {
+      name: `${language}_server_status`,
+      description: `Show ${displayName} language server readiness and indexing progress`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_run_tests`,
+      description: `List and run ${displayName} tests with pass/fail results`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_get_call_hierarchy`,
+      description: `Show callers and callees of a ${displayName} function`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_find_implementations`,
+      description: `Find implementations of a ${displayName} trait, interface or method`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_get_type_definition`,
+      description: `Go to the definition of a ${displayName} symbol's type`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_get_type_hierarchy`,
+      description: `Show supertypes and subtypes of a ${displayName} type`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_get_inlay_hints`,
+      description: `Show ${displayName} code annotated with inferred types and parameter names`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_get_enclosing_range`,
+      description: `Get the source of the ${displayName} block enclosing a line`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_move_file`,
+      description: `Move a ${displayName} file and update references to it`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_extract_function`,
+      description: `Extract ${displayName} lines into a new function`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_extract_variable`,
+      description: `Extract a ${displayName} expression into a new variable`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_inline_symbol`,
+      description: `Inline a ${displayName} variable or function at its use sites`,
+      category: "lsp",
+      requiresLSP: true,
+    },
+    {
+      name: `${language}_organize_imports`,
+      description: `Organize ${displayName} imports and remove unused ones`,
+      category: "lsp",
+      requiresLSP: true,
+    },
   ];
 
+  // rust-analyzer extension requests
+  if (language === "rust") {
+    languageTools.push(
+      {
+        name: `${language}_expand_macro`,
+        description: "Show the recursive expansion of a Rust macro call",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_syntax_tree`,
+        description: "Show the rust-analyzer syntax tree of a file or line range",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_view_hir`,
+        description: "Show the HIR of the Rust function at a position",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_view_item_tree`,
+        description: "Show the item tree of a Rust file",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_parent_module`,
+        description: "Go to the parent module declaration of a file or inline module",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_open_cargo_toml`,
+        description: "Find the Cargo.toml of the crate a file belongs to",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_related_tests`,
+        description: "Find the tests that exercise an item",
+        category: "lsp",
+        requiresLSP: true,
+      },
+      {
+        name: `${language}_structural_replace`,
+        description: "Rewrite code matching a structural search pattern",
+        category: "lsp",
+        requiresLSP: true,
+      },
+    );
+  }
+
   return {
     name: `${language}_list_tools`,
     description: `List all available ${displayName} MCP tools with descriptions and categories`,
@@ -30,6 +30,13 @@ import { lspGetCompletionTool } from "../lsp/tools/lspGetCompletion.ts";
 import { lspGetSignatureHelpTool } from "../lsp/tools/lspGetSignatureHelp.ts";
 import { lspFormatDocumentTool } from "../lsp/tools/lspFormatDocument.ts";
 import { lspGetCodeActionsTool } from "../lsp/tools/lspGetCodeActions.ts";
+import { lspApplyCodeActionTool } from "../lsp/tools/lspApplyCodeAction.ts";
+import { lspExtractFunctionTool } from "../lsp/tools/lspExtractFunction.ts";
+import { lspExtractVariableTool } from "../lsp/tools/lspExtractVariable.ts";
+import { lspInlineSymbolTool } from "../lsp/tools/lspInlineSymbol.ts";
+import { lspOrganizeImportsTool } from "../lsp/tools/lspOrganizeImports.ts";
+import { lspRunTestsTool } from "../lsp/tools/lspRunTests.ts";
+import { lspGetTypeHierarchyTool } from "../lsp/tools/lspGetTypeHierarchy.ts";
 import { listToolsTool } from "./tools/listTools.ts";
 import * as fs from "node:fs";
 import * as path from "node:path";
@@ -81,6 +88,13 @@ const tools: ToolDef<any>[] = [
         lspGetSignatureHelpTool,
         lspFormatDocumentTool,
         lspGetCodeActionsTool,
+        lspApplyCodeActionTool,
+        lspExtractFunctionTool,
+        lspExtractVariableTool,
+        lspInlineSymbolTool,
+        lspOrganizeImportsTool,
+        lspRunTestsTool,
+        lspGetTypeHierarchyTool,
       ]
     : []),
 ];
@@ -5,11 +5,11 @@
 export interface ToolAvailability {
   typescriptOnly: string[];
   lspBased: string[];
+  rustAnalyzerOnly: string[];
 }
 
 export const TOOL_AVAILABILITY: ToolAvailability = {
   typescriptOnly: [
-    "lsmcp_move_file",
     "lsmcp_move_directory", 
     "lsmcp_delete_symbol",
     "lsmcp_get_module_symbols",
@@ -23,6 +23,7 @@ export const TOOL_AVAILABILITY: ToolAvailability = {
     "lsmcp_find_references",
     "lsmcp_get_definitions",
     "lsmcp_get_diagnostics",
+    "lsmcp_get_workspace_diagnostics",
     "lsmcp_get_hover",
     "lsmcp_rename_symbol",
     "lsmcp_get_document_symbols",
@@ -30,7 +31,32 @@ export const TOOL_AVAILABILITY: ToolAvailability = {
     "lsmcp_get_completion",
     "lsmcp_get_signature_help",
     "lsmcp_get_code_actions",
+    "lsmcp_apply_code_action",
     "lsmcp_format_document",
+    "lsmcp_server_status",
+    "lsmcp_run_tests",
+    "lsmcp_get_call_hierarchy",
+    "lsmcp_find_implementations",
+    "lsmcp_get_type_definition",
+    "lsmcp_get_type_hierarchy",
+    "lsmcp_get_inlay_hints",
+    "lsmcp_get_enclosing_range",
+    "lsmcp_move_file",
+    "lsmcp_extract_function",
+    "lsmcp_extract_variable",
+    "lsmcp_inline_symbol",
+    "lsmcp_delete_unused_symbol",
+    "lsmcp_organize_imports",
+  ],
+  rustAnalyzerOnly: [
+    "lsmcp_rust_expand_macro",
+    "lsmcp_rust_syntax_tree",
+    "lsmcp_rust_view_hir",
+    "lsmcp_rust_view_item_tree",
+    "lsmcp_rust_parent_module",
+    "lsmcp_rust_open_cargo_toml",
+    "lsmcp_rust_related_tests",
+    "lsmcp_structural_replace",
   ],
 };
 
@@ -54,6 +80,15 @@ For TypeScript-specific features with other languages, consider:
 - Using your IDE's built-in refactoring features`;
   }
   
+  if (TOOL_AVAILABILITY.rustAnalyzerOnly.includes(toolName)) {
+    return `Error: Tool '${toolName}' is only available with rust-analyzer.
+
+This tool uses rust-analyzer LSP extensions that other language servers do not implement.
+
+Available tools for ${currentLanguage}:
+${availableTools.map(t => `  - ${t}`).join('\n')}`;
+  }
+
   return `Error: Tool '${toolName}' is not available for ${currentLanguage}.
 
 Your LSP server may not support this feature.
@@ -81,6 +116,7 @@ export function getLanguageFromLSPCommand(lspCommand: string): string {
     "rls": "Rust",
     "hls": "Haskell",
     "omnisharp": "C#",
+    "moonbit-lsp": "MoonBit",
   };
   
   const command = lspCommand.split(" ")[0];
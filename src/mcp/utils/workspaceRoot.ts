@@ -0,0 +1,208 @@
+/**
+ * Workspace root detection for LSP servers
+ */
+
+import { existsSync } from "fs";
+import { dirname, join, resolve } from "path";
+import { isCargoWorkspaceRoot } from "../../rust/cargo.ts";
+
+interface RootMarkerSpec {
+  // Files that mark a project root (nearest one wins)
+  markers: string[];
+  // Returns true if an ancestor directory is a workspace enclosing the nearest project
+  isWorkspaceRoot?: (dir: string) => boolean;
+}
+
+export interface WorkspaceRoot {
+  root: string;
+  // Marker file that selected the root, or null when falling back to the start directory
+  marker: string | null;
+}
+
+const ROOT_MARKERS: Record<string, RootMarkerSpec> = {
+  rust: {
+    markers: ["Cargo.toml"],
+    isWorkspaceRoot: isCargoWorkspaceRoot,
+  },
+  go: {
+    markers: ["go.mod", "go.work"],
+    isWorkspaceRoot: (dir) => existsSync(join(dir, "go.work")),
+  },
+  python: {
+    markers: ["pyproject.toml", "setup.py", "setup.cfg"],
+  },
+  moonbit: {
+    markers: ["moon.mod.json"],
+  },
+  typescript: {
+    markers: ["tsconfig.json", "package.json"],
+  },
+  // getLanguageFromLSPCommand's name for `deno lsp`
+  "typescript/deno": {
+    markers: ["deno.json", "deno.jsonc"],
+  },
+};
+
+function findMarker(dir: string, markers: string[]): string | null {
+  return markers.find((marker) => existsSync(join(dir, marker))) ?? null;
+}
+
+/**
+ * Find the workspace root for a language by walking up from startDir.
+ * The nearest directory with a project marker is used unless an ancestor
+ * declares a workspace (e.g. Cargo `[workspace]`, `go.work`) that encloses it.
+ * @param startDir Directory to start searching from
+ * @param language Language name (case-insensitive); languages without known
+ *   markers keep startDir as the root
+ * @returns The detected root and the marker that selected it
+ */
+export function findWorkspaceRoot(
+  startDir: string,
+  language?: string
+): WorkspaceRoot {
+  const start = resolve(startDir);
+  const spec = language ? ROOT_MARKERS[language.toLowerCase()] : undefined;
+  if (!spec) {
+    return { root: start, marker: null };
+  }
+  const markers = spec.markers;
+
+  let nearest: WorkspaceRoot | null = null;
+  let currentDir = start;
+
+  while (true) {
+    const marker = findMarker(currentDir, markers);
+    if (marker && !nearest) {
+      nearest = { root: currentDir, marker };
+      if (!spec.isWorkspaceRoot) {
+        return nearest;
+      }
+    }
+    if (nearest && spec.isWorkspaceRoot?.(currentDir)) {
+      return { root: currentDir, marker: findMarker(currentDir, markers) };
+    }
+
+    const parentDir = dirname(currentDir);
+    if (parentDir === currentDir) {
+      break;
+    }
+    currentDir = parentDir;
+  }
+
+  return nearest ?? { root: start, marker: null };
+}
+
+/**
+ * Find the workspace folder serving a tool call. A `root` may span several
+ * projects (e.g. a monorepo of Cargo workspaces), so the project enclosing
+ * `filePath` wins when it has one; otherwise the project of `root` is used.
+ * @param root Root directory of the tool call
+ * @param filePath File of the tool call, relative to root or absolute
+ * @param language Language name, as for findWorkspaceRoot
+ */
+export function findWorkspaceFolder(
+  root: string,
+  filePath?: string,
+  language?: string
+): string {
+  if (filePath) {
+    const fileRoot = findWorkspaceRoot(dirname(resolve(root, filePath)), language);
+    if (fileRoot.marker) {
+      return fileRoot.root;
+    }
+  }
+  return findWorkspaceRoot(root, language).root;
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect, beforeAll, afterAll } = import.meta.vitest;
+  const { default: fs } = await import("fs/promises");
+  const { tmpdir } = await import("os");
+
+  describe("findWorkspaceRoot", () => {
+    let tmpDir: string;
+
+    beforeAll(async () => {
+      tmpDir = await fs.mkdtemp(join(tmpdir(), "lsmcp-root-"));
+      // Cargo workspace with a member crate
+      await fs.mkdir(join(tmpDir, "cargo/crates/member/src"), { recursive: true });
+      await fs.writeFile(join(tmpDir, "cargo/Cargo.toml"), `[workspace]\nmembers = ["crates/*"]\n`);
+      await fs.writeFile(join(tmpDir, "cargo/crates/member/Cargo.toml"), `[package]\nname = "member"\n`);
+      // Standalone crate
+      await fs.mkdir(join(tmpDir, "single/src"), { recursive: true });
+      await fs.writeFile(join(tmpDir, "single/Cargo.toml"), `[package]\nname = "single"\n`);
+      // Go workspace
+      await fs.mkdir(join(tmpDir, "go/mod"), { recursive: true });
+      await fs.writeFile(join(tmpDir, "go/go.work"), "go 1.21\n");
+      await fs.writeFile(join(tmpDir, "go/mod/go.mod"), "module example.com/mod\n");
+      // MoonBit module
+      await fs.mkdir(join(tmpDir, "moon/src/lib"), { recursive: true });
+      await fs.writeFile(join(tmpDir, "moon/moon.mod.json"), "{}");
+      // Deno project
+      await fs.mkdir(join(tmpDir, "deno/src"), { recursive: true });
+      await fs.writeFile(join(tmpDir, "deno/deno.json"), "{}");
+    });
+
+    afterAll(async () => {
+      await fs.rm(tmpDir, { recursive: true, force: true });
+    });
+
+    it("should walk up from a member crate to the cargo workspace", () => {
+      const result = findWorkspaceRoot(join(tmpDir, "cargo/crates/member/src"), "Rust");
+      expect(result).toEqual({ root: join(tmpDir, "cargo"), marker: "Cargo.toml" });
+    });
+
+    it("should use the nearest Cargo.toml outside a workspace", () => {
+      const result = findWorkspaceRoot(join(tmpDir, "single/src"), "rust");
+      expect(result).toEqual({ root: join(tmpDir, "single"), marker: "Cargo.toml" });
+    });
+
+    it("should prefer go.work over go.mod", () => {
+      const result = findWorkspaceRoot(join(tmpDir, "go/mod"), "go");
+      expect(result).toEqual({ root: join(tmpDir, "go"), marker: "go.work" });
+    });
+
+    it("should find the module root of a MoonBit project", () => {
+      const result = findWorkspaceRoot(join(tmpDir, "moon/src/lib"), "moonbit");
+      expect(result).toEqual({ root: join(tmpDir, "moon"), marker: "moon.mod.json" });
+    });
+
+    it("should find roots for the languages detected from LSP commands", async () => {
+      const { getLanguageFromLSPCommand } = await import("./languageSupport.ts");
+
+      expect(
+        findWorkspaceRoot(join(tmpDir, "moon/src/lib"), getLanguageFromLSPCommand("moonbit-lsp"))
+      ).toEqual({ root: join(tmpDir, "moon"), marker: "moon.mod.json" });
+      expect(
+        findWorkspaceRoot(join(tmpDir, "deno/src"), getLanguageFromLSPCommand("deno lsp"))
+      ).toEqual({ root: join(tmpDir, "deno"), marker: "deno.json" });
+    });
+
+    it("should keep the start directory for languages without markers", () => {
+      const result = findWorkspaceRoot(join(tmpDir, "moon/src/lib"), "Lua");
+      expect(result).toEqual({ root: join(tmpDir, "moon/src/lib"), marker: null });
+      expect(findWorkspaceRoot(join(tmpDir, "moon/src/lib"))).toEqual({
+        root: join(tmpDir, "moon/src/lib"),
+        marker: null,
+      });
+    });
+
+    it("should fall back to the start directory", () => {
+      const result = findWorkspaceRoot(tmpDir, "python");
+      expect(result).toEqual({ root: tmpDir, marker: null });
+    });
+
+    it("should route a file to the project enclosing it", () => {
+      expect(findWorkspaceFolder(tmpDir, "single/src/lib.rs", "rust")).toBe(join(tmpDir, "single"));
+      expect(findWorkspaceFolder(tmpDir, "cargo/crates/member/src/lib.rs", "rust")).toBe(
+        join(tmpDir, "cargo")
+      );
+      expect(findWorkspaceFolder(join(tmpDir, "single"), "../README.md", "rust")).toBe(
+        join(tmpDir, "single")
+      );
+      expect(findWorkspaceFolder(join(tmpDir, "single"), undefined, "rust")).toBe(
+        join(tmpDir, "single")
+      );
+    });
+  });
+}
@@ -0,0 +1,324 @@
+/**
+ * Cargo project helpers
+ */
+
+import { execFile, spawn } from "child_process";
+import { existsSync, readFileSync } from "fs";
+import { join } from "path";
+
+const CARGO_TIMEOUT = 300000;
+
+// Subset of `cargo metadata --format-version 1` output
+export interface CargoTarget {
+  name: string;
+  kind: string[];
+  src_path: string;
+}
+
+export interface CargoPackage {
+  name: string;
+  manifest_path: string;
+  targets: CargoTarget[];
+}
+
+export interface CargoMetadata {
+  workspace_root: string;
+  target_directory: string;
+  packages: CargoPackage[];
+}
+
+// A test executable built by `cargo test --no-run`
+export interface CargoTestArtifact {
+  targetName: string;
+  kind: string[];
+  executable: string;
+  manifestPath: string;
+}
+
+/**
+ * Check whether a directory holds a Cargo.toml that declares a `[workspace]`
+ * @param dir Directory to check
+ * @returns true if dir is the root of a Cargo workspace
+ */
+export function isCargoWorkspaceRoot(dir: string): boolean {
+  const manifestPath = join(dir, "Cargo.toml");
+  if (!existsSync(manifestPath)) {
+    return false;
+  }
+  try {
+    const manifest = readFileSync(manifestPath, "utf-8");
+    return /^\s*\[workspace\]/m.test(manifest);
+  } catch {
+    return false;
+  }
+}
+
+export interface RunCargoOptions {
+  timeout?: number;
+  // Resolve with stdout even on a non-zero exit, e.g. `cargo check` reporting compile errors
+  allowFailure?: boolean;
+}
+
+/**
+ * Run cargo and collect stdout
+ * @throws When cargo exits with a non-zero status (without output when
+ *   allowFailure is set) or times out
+ */
+export function runCargo(
+  args: string[],
+  cwd: string,
+  { timeout = CARGO_TIMEOUT, allowFailure = false }: RunCargoOptions = {}
+): Promise<string> {
+  return new Promise<string>((resolve, reject) => {
+    const proc = spawn("cargo", args, { cwd, stdio: ["ignore", "pipe", "pipe"] });
+
+    let stdout = "";
+    let stderr = "";
+    const timer = setTimeout(() => {
+      proc.kill();
+      reject(new Error(`cargo ${args[0]} timed out after ${timeout / 1000} seconds`));
+    }, timeout);
+
+    proc.stdout.on("data", (data: Buffer) => {
+      stdout += data.toString();
+    });
+    proc.stderr.on("data", (data: Buffer) => {
+      stderr += data.toString();
+    });
+    proc.on("error", (error) => {
+      clearTimeout(timer);
+      reject(error);
+    });
+    proc.on("close", (code) => {
+      clearTimeout(timer);
+      if (code !== 0 && !(allowFailure && stdout)) {
+        reject(new Error(`cargo ${args[0]} failed: ${stderr.trim()}`));
+        return;
+      }
+      resolve(stdout);
+    });
+  });
+}
+
+/**
+ * Read workspace metadata (without dependencies) for the project containing dir
+ */
+export async function getCargoMetadata(dir: string): Promise<CargoMetadata> {
+  const output = await runCargo(
+    ["metadata", "--format-version", "1", "--no-deps"],
+    dir
+  );
+  return JSON.parse(output) as CargoMetadata;
+}
+
+/**
+ * Pick the binary target to build: the named one, or the only one in the workspace
+ */
+export function selectBinTarget(
+  metadata: CargoMetadata,
+  bin?: string
+): { pkg: CargoPackage; target: CargoTarget } {
+  const bins = metadata.packages.flatMap((pkg) =>
+    pkg.targets
+      .filter((target) => target.kind.includes("bin"))
+      .map((target) => ({ pkg, target }))
+  );
+
+  if (bin !== undefined) {
+    const found = bins.find(({ target }) => target.name === bin);
+    if (!found) {
+      throw new Error(
+        `No binary target named "${bin}". Available: ${bins.map(({ target }) => target.name).join(", ") || "none"}`
+      );
+    }
+    return found;
+  }
+
+  if (bins.length !== 1) {
+    throw new Error(
+      bins.length === 0
+        ? `No binary targets found in ${metadata.workspace_root}`
+        : `Multiple binary targets found, specify one of: ${bins.map(({ target }) => target.name).join(", ")}`
+    );
+  }
+  return bins[0];
+}
+
+/**
+ * Build a binary target with `cargo build` and return the path of the executable,
+ * resolved from the metadata target_directory
+ */
+export async function buildCargoBinary(
+  dir: string,
+  options: { bin?: string; release?: boolean } = {}
+): Promise<string> {
+  const metadata = await getCargoMetadata(dir);
+  const { pkg, target } = selectBinTarget(metadata, options.bin);
+
+  const args = ["build", "-p", pkg.name, "--bin", target.name];
+  if (options.release) {
+    args.push("--release");
+  }
+  await runCargo(args, metadata.workspace_root);
+
+  const exe = process.platform === "win32" ? `${target.name}.exe` : target.name;
+  return join(
+    metadata.target_directory,
+    options.release ? "release" : "debug",
+    exe
+  );
+}
+
+/**
+ * Collect test executables from `cargo test --no-run --message-format=json` output
+ */
+export function parseTestArtifacts(output: string): CargoTestArtifact[] {
+  const artifacts: CargoTestArtifact[] = [];
+  for (const line of output.split("\n")) {
+    if (!line.startsWith("{")) continue;
+    let message: any;
+    try {
+      message = JSON.parse(line);
+    } catch {
+      continue;
+    }
+    if (
+      message.reason === "compiler-artifact" &&
+      message.profile?.test &&
+      message.executable
+    ) {
+      artifacts.push({
+        targetName: message.target.name,
+        kind: message.target.kind,
+        executable: message.executable,
+        manifestPath: message.manifest_path,
+      });
+    }
+  }
+  return artifacts;
+}
+
+/**
+ * Build test executables without running them
+ */
+export async function buildCargoTests(
+  dir: string,
+  options: { package?: string } = {}
+): Promise<CargoTestArtifact[]> {
+  const args = ["test", "--no-run", "--message-format=json"];
+  if (options.package) {
+    args.push("-p", options.package);
+  }
+  return parseTestArtifacts(await runCargo(args, dir));
+}
+
+/**
+ * List the tests of a test executable (`<executable> --list`)
+ */
+export function listTests(executable: string, cwd?: string): Promise<string[]> {
+  return new Promise<string[]>((resolve, reject) => {
+    execFile(executable, ["--list"], { cwd }, (error, stdout) => {
+      if (error) {
+        reject(error);
+        return;
+      }
+      resolve(
+        stdout
+          .split("\n")
+          .map((line) => line.match(/^(.+): test$/)?.[1])
+          .filter((name): name is string => name !== undefined)
+      );
+    });
+  });
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+  const { default: path } = await import("path");
+
+  describe("isCargoWorkspaceRoot", () => {
+    const rustProject = path.resolve(import.meta.dirname, "../../examples/rust-project");
+
+    it("should return false for a single-package manifest", () => {
+      expect(isCargoWorkspaceRoot(rustProject)).toBe(false);
+    });
+
+    it("should return false for a directory without Cargo.toml", () => {
+      expect(isCargoWorkspaceRoot(path.join(rustProject, "src"))).toBe(false);
+    });
+  });
+
+  describe("selectBinTarget", () => {
+    const metadata: CargoMetadata = {
+      workspace_root: "/work",
+      target_directory: "/work/target",
+      packages: [
+        {
+          name: "app",
+          manifest_path: "/work/app/Cargo.toml",
+          targets: [
+            { name: "app", kind: ["lib"], src_path: "/work/app/src/lib.rs" },
+            { name: "app", kind: ["bin"], src_path: "/work/app/src/main.rs" },
+          ],
+        },
+        {
+          name: "tools",
+          manifest_path: "/work/tools/Cargo.toml",
+          targets: [
+            { name: "gen", kind: ["bin"], src_path: "/work/tools/src/bin/gen.rs" },
+          ],
+        },
+      ],
+    };
+
+    it("should select a binary target by name", () => {
+      const { pkg, target } = selectBinTarget(metadata, "gen");
+      expect(pkg.name).toBe("tools");
+      expect(target.src_path).toBe("/work/tools/src/bin/gen.rs");
+    });
+
+    it("should require a name when there are several binaries", () => {
+      expect(() => selectBinTarget(metadata)).toThrow("specify one of: app, gen");
+      expect(() => selectBinTarget(metadata, "missing")).toThrow(
+        'No binary target named "missing"'
+      );
+    });
+
+    it("should select the only binary of a single-package project", () => {
+      const single = { ...metadata, packages: [metadata.packages[0]] };
+      expect(selectBinTarget(single).target.name).toBe("app");
+    });
+  });
+
+  describe("parseTestArtifacts", () => {
+    it("should collect executables of test profile artifacts", () => {
+      const output = [
+        JSON.stringify({
+          reason: "compiler-artifact",
+          manifest_path: "/work/Cargo.toml",
+          target: { name: "app", kind: ["lib"] },
+          profile: { test: false },
+          executable: null,
+        }),
+        JSON.stringify({
+          reason: "compiler-artifact",
+          manifest_path: "/work/Cargo.toml",
+          target: { name: "app", kind: ["lib"] },
+          profile: { test: true },
+          executable: "/work/target/debug/deps/app-0123",
+        }),
+        JSON.stringify({ reason: "build-finished", success: true }),
+        "",
+      ].join("\n");
+
+      expect(parseTestArtifacts(output)).toEqual([
+        {
+          targetName: "app",
+          kind: ["lib"],
+          executable: "/work/target/debug/deps/app-0123",
+          manifestPath: "/work/Cargo.toml",
+        },
+      ]);
+    });
+  });
+}
@@ -0,0 +1,235 @@
+/**
+ * Run `cargo check` / `cargo clippy` and convert compiler messages to diagnostics
+ */
+
+import path from "path";
+import type { Diagnostic } from "../lsp/tools/lspGetDiagnostics.ts";
+import { findWorkspaceRoot } from "../mcp/utils/workspaceRoot.ts";
+import { runCargo } from "./cargo.ts";
+import { debug } from "../mcp/_mcplib.ts";
+
+export type CargoCheckCommand = "check" | "clippy";
+
+const CARGO_CHECK_TIMEOUT = 120000;
+
+// Subset of rustc's JSON diagnostic format
+interface RustcSpan {
+  file_name: string;
+  line_start: number;
+  line_end: number;
+  column_start: number;
+  column_end: number;
+  is_primary: boolean;
+}
+
+interface RustcMessage {
+  message: string;
+  code: { code: string } | null;
+  level: string;
+  spans: RustcSpan[];
+}
+
+interface CargoMessage {
+  reason: string;
+  message?: RustcMessage;
+}
+
+const LEVEL_MAP: Record<string, Diagnostic["severity"]> = {
+  error: "error",
+  "error: internal compiler error": "error",
+  warning: "warning",
+  note: "information",
+  help: "hint",
+};
+
+/**
+ * Parse `cargo --message-format=json` output into diagnostics grouped by absolute file path
+ * @param output stdout of cargo
+ * @param workspaceRoot Directory cargo was run in (span paths are relative to it)
+ * @param source Diagnostic source label
+ */
+export function parseCargoMessages(
+  output: string,
+  workspaceRoot: string,
+  source: string = "rustc"
+): Map<string, Diagnostic[]> {
+  const diagnostics = new Map<string, Diagnostic[]>();
+
+  for (const line of output.split("\n")) {
+    if (!line.startsWith("{")) {
+      continue;
+    }
+
+    let parsed: CargoMessage;
+    try {
+      parsed = JSON.parse(line) as CargoMessage;
+    } catch {
+      continue;
+    }
+    if (parsed.reason !== "compiler-message" || !parsed.message) {
+      continue;
+    }
+
+    const { message } = parsed;
+    const severity = LEVEL_MAP[message.level];
+    const span = message.spans.find((s) => s.is_primary) ?? message.spans[0];
+    // Summary messages like "aborting due to 2 previous errors" have no span
+    if (!severity || !span) {
+      continue;
+    }
+
+    const filePath = path.resolve(workspaceRoot, span.file_name);
+    const fileDiagnostics = diagnostics.get(filePath) ?? [];
+    fileDiagnostics.push({
+      severity,
+      line: span.line_start,
+      column: span.column_start,
+      endLine: span.line_end,
+      endColumn: span.column_end,
+      message: message.message,
+      source,
+      code: message.code?.code,
+    });
+    diagnostics.set(filePath, fileDiagnostics);
+  }
+
+  return diagnostics;
+}
+
+/**
+ * Run cargo check or clippy for the workspace containing startDir
+ * @returns Diagnostics grouped by absolute file path
+ */
+export async function runCargoCheck(
+  startDir: string,
+  command: CargoCheckCommand = "check",
+  timeout: number = CARGO_CHECK_TIMEOUT
+): Promise<Map<string, Diagnostic[]>> {
+  const { root } = findWorkspaceRoot(startDir, "rust");
+
+  // cargo exits non-zero when there are compile errors, which is expected here
+  const output = await runCargo(
+    [command, "--message-format=json", "--all-targets"],
+    root,
+    { timeout, allowFailure: true }
+  );
+
+  debug(`[cargo] ${command} finished in ${root}`);
+  return parseCargoMessages(output, root, command === "clippy" ? "clippy" : "rustc");
+}
+
+/**
+ * Merge two diagnostic lists, dropping entries that report the same
+ * message at the same position (e.g. rust-analyzer flycheck and cargo check)
+ */
+export function mergeDiagnostics(
+  primary: Diagnostic[],
+  secondary: Diagnostic[]
+): Diagnostic[] {
+  const key = (d: Diagnostic) =>
+    `${d.line}:${d.column}:${d.severity}:${d.message.split("\n")[0].trim()}`;
+  const seen = new Set(primary.map(key));
+  const merged = [...primary];
+  for (const diag of secondary) {
+    if (!seen.has(key(diag))) {
+      seen.add(key(diag));
+      merged.push(diag);
+    }
+  }
+  return merged.sort((a, b) => a.line - b.line || a.column - b.column);
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  const compilerMessage = (
+    level: string,
+    message: string,
+    code: string | null,
+    span: Partial<RustcSpan> | null
+  ) =>
+    JSON.stringify({
+      reason: "compiler-message",
+      message: {
+        message,
+        code: code ? { code } : null,
+        level,
+        spans: span
+          ? [
+              {
+                file_name: "src/main.rs",
+                line_start: 5,
+                line_end: 5,
+                column_start: 20,
+                column_end: 24,
+                is_primary: true,
+                ...span,
+              },
+            ]
+          : [],
+      },
+    });
+
+  describe("parseCargoMessages", () => {
+    it("should convert compiler messages to diagnostics", () => {
+      const output = [
+        JSON.stringify({ reason: "compiler-artifact", target: {} }),
+        compilerMessage("error", "mismatched types", "E0308", {}),
+        compilerMessage("warning", "unused variable: `x`", null, {
+          file_name: "src/lib.rs",
+          line_start: 2,
+          line_end: 2,
+          column_start: 9,
+          column_end: 10,
+        }),
+        compilerMessage("error", "aborting due to 1 previous error", null, null),
+        "not json",
+      ].join("\n");
+
+      const result = parseCargoMessages(output, "/work");
+
+      expect(result.get("/work/src/main.rs")).toEqual([
+        {
+          severity: "error",
+          line: 5,
+          column: 20,
+          endLine: 5,
+          endColumn: 24,
+          message: "mismatched types",
+          source: "rustc",
+          code: "E0308",
+        },
+      ]);
+      expect(result.get("/work/src/lib.rs")?.[0]).toMatchObject({
+        severity: "warning",
+        line: 2,
+        code: undefined,
+      });
+      expect(result.size).toBe(2);
+    });
+  });
+
+  describe("mergeDiagnostics", () => {
+    const diag = (line: number, message: string, source: string): Diagnostic => ({
+      severity: "error",
+      line,
+      column: 1,
+      endLine: line,
+      endColumn: 5,
+      message,
+      source,
+    });
+
+    it("should drop duplicates and keep position order", () => {
+      const merged = mergeDiagnostics(
+        [diag(3, "mismatched types", "rust-analyzer")],
+        [diag(3, "mismatched types\nexpected `i32`", "rustc"), diag(1, "other", "rustc")]
+      );
+
+      expect(merged.map((d) => `${d.line}:${d.source}`)).toEqual([
+        "1:rustc",
+        "3:rust-analyzer",
+      ]);
+    });
+  });
+}
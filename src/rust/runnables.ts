@@ -0,0 +1,306 @@
+/**
+ * rust-analyzer runnables (`experimental/runnables`) and libtest output parsing
+ */
+
+import path from "path";
+import { fileURLToPath } from "url";
+import type { LocationLink, Position } from "vscode-languageserver-types";
+import type { LSPClient } from "../lsp/lspTypes.ts";
+import type { TestCaseResult } from "../common/testResults.ts";
+
+// rust-analyzer LSP extension types (see docs/dev/lsp-extensions.md)
+interface CargoRunnableArgs {
+  environment?: Record<string, string>;
+  cwd?: string;
+  workspaceRoot?: string;
+  overrideCargo?: string;
+  cargoArgs: string[];
+  // Removed in newer rust-analyzer versions, merged into cargoArgs
+  cargoExtraArgs?: string[];
+  executableArgs: string[];
+}
+
+interface ShellRunnableArgs {
+  environment?: Record<string, string>;
+  cwd: string;
+  program: string;
+  args: string[];
+}
+
+export type RustRunnable =
+  | { label: string; location?: LocationLink; kind: "cargo"; args: CargoRunnableArgs }
+  | { label: string; location?: LocationLink; kind: "shell"; args: ShellRunnableArgs };
+
+export interface RunnableCommand {
+  command: string;
+  args: string[];
+  cwd: string;
+  env?: Record<string, string>;
+  // Directory that compiler-reported relative paths are resolved against
+  sourceRoot: string;
+}
+
+/**
+ * Request runnables for a file, or only those enclosing a position
+ */
+export async function getRustRunnables(
+  client: LSPClient,
+  uri: string,
+  position?: Position
+): Promise<RustRunnable[]> {
+  const result = await client.sendRequest<RustRunnable[] | null>(
+    "experimental/runnables",
+    { textDocument: { uri }, position }
+  );
+  return result ?? [];
+}
+
+/**
+ * Build the command line that runs a runnable
+ */
+export function runnableToCommand(
+  runnable: RustRunnable,
+  defaultCwd: string
+): RunnableCommand {
+  if (runnable.kind === "shell") {
+    const { program, args, cwd, environment } = runnable.args;
+    return { command: program, args, cwd, env: environment, sourceRoot: cwd };
+  }
+
+  const {
+    cargoArgs,
+    cargoExtraArgs = [],
+    executableArgs,
+    overrideCargo,
+    cwd,
+    workspaceRoot,
+    environment,
+  } = runnable.args;
+  const args = [...cargoArgs, ...cargoExtraArgs];
+  if (executableArgs.length > 0) {
+    args.push("--", ...executableArgs);
+  }
+  const runDir = cwd ?? workspaceRoot ?? defaultCwd;
+  return {
+    command: overrideCargo ?? "cargo",
+    args,
+    cwd: runDir,
+    env: environment,
+    // rustc is invoked from the workspace root, so panic paths are relative to it
+    sourceRoot: workspaceRoot ?? runDir,
+  };
+}
+
+/**
+ * Location of the runnable's target as an absolute path and 1-based line
+ */
+export function getRunnableLocation(
+  runnable: RustRunnable
+): { filePath: string; line: number } | undefined {
+  if (!runnable.location) {
+    return undefined;
+  }
+  return {
+    filePath: fileURLToPath(runnable.location.targetUri),
+    line: runnable.location.targetSelectionRange.start.line + 1,
+  };
+}
+
+const TEST_LINE = /^test (\S+) \.\.\. (ok|FAILED|ignored)/;
+// "panicked at src/lib.rs:43:9:" (Rust 1.73+) or "panicked at 'msg', src/lib.rs:43:9"
+const PANIC_LOCATION = /panicked at (?:'[\s\S]*?', )?([^\s:'][^:]*):(\d+):\d+/;
+
+/**
+ * Parse libtest output (`cargo test`) into per-test results.
+ * Failure messages are taken from the `---- name stdout ----` sections, and
+ * panic locations are resolved against sourceRoot.
+ */
+export function parseLibtestOutput(
+  output: string,
+  sourceRoot: string
+): TestCaseResult[] {
+  const lines = output.split("\n");
+  const results = new Map<string, TestCaseResult>();
+
+  for (const line of lines) {
+    const match = line.match(TEST_LINE);
+    if (match) {
+      const [, name, status] = match;
+      results.set(name, {
+        name,
+        status: status === "ok" ? "passed" : status === "FAILED" ? "failed" : "ignored",
+      });
+    }
+  }
+
+  // Collect the captured output of each failed test
+  for (let i = 0; i < lines.length; i++) {
+    const header = lines[i].match(/^---- (\S+) stdout ----$/);
+    const result = header && results.get(header[1]);
+    if (!result || result.status !== "failed") {
+      continue;
+    }
+
+    const body: string[] = [];
+    for (i++; i < lines.length; i++) {
+      if (/^---- \S+ stdout ----$/.test(lines[i]) || /^(failures|successes):$/.test(lines[i])) {
+        i--;
+        break;
+      }
+      body.push(lines[i]);
+    }
+
+    const text = body.join("\n");
+    const panic = text.match(PANIC_LOCATION);
+    if (panic) {
+      result.location = {
+        filePath: resolveSourcePath(panic[1], sourceRoot),
+        line: parseInt(panic[2], 10),
+      };
+    }
+    // Drop the panic header, backtraces and the RUST_BACKTRACE hint
+    const backtrace = body.indexOf("stack backtrace:");
+    result.message = (backtrace === -1 ? body : body.slice(0, backtrace))
+      .filter((line) => !line.startsWith("note: run with `RUST_BACKTRACE"))
+      .map((line) => line.replace(/^thread '.*?'(?: \(\d+\))? panicked at .*:\d+:\d+:$/, ""))
+      .join("\n")
+      .trim();
+  }
+
+  return Array.from(results.values());
+}
+
+function resolveSourcePath(filePath: string, sourceRoot: string): string {
+  return path.isAbsolute(filePath) ? filePath : path.join(sourceRoot, filePath);
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("runnableToCommand", () => {
+    it("should pass executable args after --", () => {
+      const command = runnableToCommand(
+        {
+          label: "test tests::test_calculator",
+          kind: "cargo",
+          args: {
+            cwd: "/work/rust-project",
+            workspaceRoot: "/work",
+            cargoArgs: ["test", "--package", "rust-project", "--lib"],
+            executableArgs: ["tests::test_calculator", "--exact", "--show-output"],
+          },
+        },
+        "/fallback"
+      );
+
+      expect(command).toEqual({
+        command: "cargo",
+        args: [
+          "test",
+          "--package",
+          "rust-project",
+          "--lib",
+          "--",
+          "tests::test_calculator",
+          "--exact",
+          "--show-output",
+        ],
+        cwd: "/work/rust-project",
+        env: undefined,
+        sourceRoot: "/work",
+      });
+    });
+
+    it("should support older cargoExtraArgs and overrideCargo", () => {
+      const command = runnableToCommand(
+        {
+          label: "check rust-project",
+          kind: "cargo",
+          args: {
+            overrideCargo: "cross",
+            cargoArgs: ["check"],
+            cargoExtraArgs: ["--all-targets"],
+            executableArgs: [],
+          },
+        },
+        "/work"
+      );
+
+      expect(command.command).toBe("cross");
+      expect(command.args).toEqual(["check", "--all-targets"]);
+      expect(command.cwd).toBe("/work");
+    });
+  });
+
+  describe("parseLibtestOutput", () => {
+    const output = [
+      "running 2 tests",
+      "test tests::test_greet ... ok",
+      "test tests::test_calculator ... FAILED",
+      "test tests::slow ... ignored",
+      "",
+      "failures:",
+      "",
+      "---- tests::test_calculator stdout ----",
+      "",
+      "thread 'tests::test_calculator' panicked at src/lib.rs:43:9:",
+      "assertion `left == right` failed",
+      "  left: 3.0",
+      " right: 4.0",
+      "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+      "",
+      "",
+      "failures:",
+      "    tests::test_calculator",
+      "",
+      "test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out",
+    ].join("\n");
+
+    it("should report each test status", () => {
+      const results = parseLibtestOutput(output, "/work");
+      expect(results.map((r) => [r.name, r.status])).toEqual([
+        ["tests::test_greet", "passed"],
+        ["tests::test_calculator", "failed"],
+        ["tests::slow", "ignored"],
+      ]);
+    });
+
+    it("should map failures to the panic location", () => {
+      const failed = parseLibtestOutput(output, "/work")[1];
+      expect(failed.location).toEqual({ filePath: "/work/src/lib.rs", line: 43 });
+      expect(failed.message).toBe(
+        "assertion `left == right` failed\n  left: 3.0\n right: 4.0"
+      );
+    });
+
+    it("should drop thread ids and backtraces from the message", () => {
+      const results = parseLibtestOutput(
+        [
+          "test tests::bad ... FAILED",
+          "---- tests::bad stdout ----",
+          "thread 'tests::bad' (5032) panicked at src/lib.rs:7:9:",
+          "assertion `left == right` failed",
+          "stack backtrace:",
+          "   0: __rustc::rust_begin_unwind",
+          "failures:",
+        ].join("\n"),
+        "/work"
+      );
+      expect(results[0].location).toEqual({ filePath: "/work/src/lib.rs", line: 7 });
+      expect(results[0].message).toBe("assertion `left == right` failed");
+    });
+
+    it("should parse the pre-1.73 panic format", () => {
+      const results = parseLibtestOutput(
+        [
+          "test tests::old ... FAILED",
+          "---- tests::old stdout ----",
+          "thread 'tests::old' panicked at 'boom', src/main.rs:7:5",
+          "failures:",
+        ].join("\n"),
+        "/work"
+      );
+      expect(results[0].location).toEqual({ filePath: "/work/src/main.rs", line: 7 });
+    });
+  });
+}
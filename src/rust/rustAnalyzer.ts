@@ -0,0 +1,88 @@
+/**
+ * rust-analyzer discovery and configuration
+ */
+
+import { execFileSync } from "child_process";
+import { existsSync } from "fs";
+import { homedir } from "os";
+import { basename, delimiter, join } from "path";
+
+/**
+ * Initialization options passed to rust-analyzer.
+ * Build scripts and proc macros are enabled so that macro-heavy crates resolve,
+ * and `cargo check` runs on save so rustc errors show up as diagnostics.
+ */
+export const RUST_ANALYZER_INITIALIZATION_OPTIONS = {
+  cargo: {
+    buildScripts: { enable: true },
+    allTargets: true,
+  },
+  procMacro: { enable: true },
+  checkOnSave: true,
+  check: { command: "check" },
+  diagnostics: { enable: true },
+  files: { excludeDirs: ["target"] },
+};
+
+/**
+ * Locate the rust-analyzer binary.
+ * Resolution order: RUST_ANALYZER_PATH, the active rustup toolchain, PATH, ~/.cargo/bin.
+ * @returns Absolute path to rust-analyzer or null if not found
+ */
+export function findRustAnalyzer(): string | null {
+  const envPath = process.env.RUST_ANALYZER_PATH;
+  if (envPath && existsSync(envPath)) {
+    return envPath;
+  }
+
+  try {
+    const rustupPath = execFileSync("rustup", ["which", "rust-analyzer"], {
+      encoding: "utf-8",
+      stdio: ["ignore", "pipe", "ignore"],
+    }).trim();
+    if (rustupPath && existsSync(rustupPath)) {
+      return rustupPath;
+    }
+  } catch {
+    // rustup is not installed or the component is missing
+  }
+
+  const binaryName = process.platform === "win32" ? "rust-analyzer.exe" : "rust-analyzer";
+  const searchDirs = [
+    ...(process.env.PATH ?? "").split(delimiter).filter(Boolean),
+    join(homedir(), ".cargo", "bin"),
+  ];
+  for (const dir of searchDirs) {
+    const candidate = join(dir, binaryName);
+    if (existsSync(candidate)) {
+      return candidate;
+    }
+  }
+
+  return null;
+}
+
+/**
+ * Check whether an LSP command starts rust-analyzer (and not another Rust
+ * server such as rls), i.e. whether rust-analyzer's extension requests are available
+ */
+export function isRustAnalyzerCommand(lspCommand: string): boolean {
+  const command = lspCommand.split(" ")[0];
+  return (
+    basename(command).startsWith("rust-analyzer") ||
+    command === process.env.RUST_ANALYZER_PATH
+  );
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  describe("isRustAnalyzerCommand", () => {
+    it("should accept rust-analyzer binaries and reject other Rust servers", () => {
+      expect(isRustAnalyzerCommand("rust-analyzer")).toBe(true);
+      expect(isRustAnalyzerCommand("/home/me/.cargo/bin/rust-analyzer")).toBe(true);
+      expect(isRustAnalyzerCommand("rls")).toBe(false);
+      expect(isRustAnalyzerCommand("/usr/bin/rls --cli")).toBe(false);
+    });
+  });
+}
@@ -0,0 +1,30 @@
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { rustExpandMacroTool } from "./rustExpandMacro.ts";
+import { rustOpenCargoTomlTool } from "./rustOpenCargoToml.ts";
+import { rustParentModuleTool } from "./rustParentModule.ts";
+import { rustRelatedTestsTool } from "./rustRelatedTests.ts";
+import { rustStructuralReplaceTool } from "./rustStructuralReplace.ts";
+import { rustSyntaxTreeTool } from "./rustSyntaxTree.ts";
+import { rustViewHirTool } from "./rustViewHir.ts";
+import { rustViewItemTreeTool } from "./rustViewItemTree.ts";
+
+export * from "./rustExpandMacro.ts";
+export * from "./rustOpenCargoToml.ts";
+export * from "./rustParentModule.ts";
+export * from "./rustRelatedTests.ts";
+export * from "./rustStructuralReplace.ts";
+export * from "./rustSyntaxTree.ts";
+export * from "./rustViewHir.ts";
+export * from "./rustViewItemTree.ts";
+
+// Tools backed by rust-analyzer LSP extensions
+export const rustAnalyzerTools: ToolDef<any>[] = [
+  rustExpandMacroTool,
+  rustSyntaxTreeTool,
+  rustViewHirTool,
+  rustViewItemTreeTool,
+  rustParentModuleTool,
+  rustOpenCargoTomlTool,
+  rustRelatedTestsTool,
+  rustStructuralReplaceTool,
+];
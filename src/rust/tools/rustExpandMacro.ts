@@ -0,0 +1,64 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  line: commonSchemas.line,
+  target: z
+    .string()
+    .describe("Macro name at the call site (e.g. 'println')")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+// Result of rust-analyzer/expandMacro
+interface ExpandedMacro {
+  name: string;
+  expansion: string;
+}
+
+async function handleExpandMacro({
+  root,
+  filePath,
+  line,
+  target,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    const client = getLSPClient()!;
+    await waitForIdleIfRequested(waitForIdle);
+
+    const result = await client.sendRequest<ExpandedMacro | null>(
+      "rust-analyzer/expandMacro",
+      { textDocument: { uri: fileUri }, position }
+    );
+    if (!result) {
+      return `No macro call found at ${filePath}:${position.line + 1}:${position.character + 1}`;
+    }
+
+    return `Recursive expansion of ${result.name}! macro\n\n\`\`\`rust\n${result.expansion}\n\`\`\``;
+  }, "Rust");
+}
+
+export const rustExpandMacroTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_expand_macro",
+  description:
+    "Show the recursive expansion of the Rust macro call at a position (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleExpandMacro(args);
+  },
+};
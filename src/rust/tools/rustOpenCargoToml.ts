@@ -0,0 +1,52 @@
+import { z } from "zod";
+import type { Location } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+import { formatNavigationTargets, toNavigationTargets } from "../../lsp/navigationTargets.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  ...waitForIdleShape,
+});
+
+async function handleOpenCargoToml({
+  root,
+  filePath,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const client = getLSPClient()!;
+    await waitForIdleIfRequested(waitForIdle);
+
+    const result = await client.sendRequest<Location | null>(
+      "experimental/openCargoToml",
+      { textDocument: { uri: fileUri } }
+    );
+    const targets = toNavigationTargets(result);
+    if (targets.length === 0) {
+      return `No Cargo.toml found for ${filePath}`;
+    }
+
+    return formatNavigationTargets(root, `Found Cargo.toml for ${filePath}`, targets);
+  }, "Rust");
+}
+
+export const rustOpenCargoTomlTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_open_cargo_toml",
+  description:
+    "Find the Cargo.toml of the crate that a Rust file belongs to (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleOpenCargoToml(args);
+  },
+};
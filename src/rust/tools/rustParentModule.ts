@@ -0,0 +1,65 @@
+import { z } from "zod";
+import type { Location, LocationLink } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  resolvePositionOrThrow,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+import { formatNavigationTargets, toNavigationTargets } from "../../lsp/navigationTargets.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  line: commonSchemas.line
+    .describe("Line number (1-based) or string to match; inline modules are resolved from here (defaults to the file's module)")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+async function handleParentModule({
+  root,
+  filePath,
+  line,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+  const position =
+    line !== undefined
+      ? resolvePositionOrThrow(content, line, undefined, filePath)
+      : { line: 0, character: 0 };
+
+  return withLSPDocument(fileUri, content, async () => {
+    const client = getLSPClient()!;
+    await waitForIdleIfRequested(waitForIdle);
+
+    const result = await client.sendRequest<Location[] | LocationLink[] | null>(
+      "experimental/parentModule",
+      { textDocument: { uri: fileUri }, position }
+    );
+    const targets = toNavigationTargets(result);
+    if (targets.length === 0) {
+      return `No parent module found for ${filePath}`;
+    }
+
+    return formatNavigationTargets(
+      root,
+      `Found ${targets.length} parent module${targets.length === 1 ? "" : "s"} for ${filePath}`,
+      targets
+    );
+  }, "Rust");
+}
+
+export const rustParentModuleTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_parent_module",
+  description:
+    "Go to the parent module of a Rust file or inline module, i.e. its `mod` declaration (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleParentModule(args);
+  },
+};
@@ -0,0 +1,82 @@
+import { z } from "zod";
+import { fileURLToPath } from "url";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+import type { RustRunnable } from "../runnables.ts";
+import { formatNavigationTargets, type NavigationTarget } from "../../lsp/navigationTargets.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  line: commonSchemas.line,
+  target: z
+    .string()
+    .describe("Name of the item on the line to find tests for")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+// Result item of rust-analyzer/relatedTests
+interface TestInfo {
+  runnable: RustRunnable;
+}
+
+async function handleRelatedTests({
+  root,
+  filePath,
+  line,
+  target,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    const client = getLSPClient()!;
+    await waitForIdleIfRequested(waitForIdle);
+
+    const result = await client.sendRequest<TestInfo[] | null>(
+      "rust-analyzer/relatedTests",
+      { textDocument: { uri: fileUri }, position }
+    );
+    const targets: NavigationTarget[] = (result ?? []).flatMap(({ runnable }) =>
+      runnable.location
+        ? [
+            {
+              filePath: fileURLToPath(runnable.location.targetUri),
+              line: runnable.location.targetSelectionRange.start.line,
+              character: runnable.location.targetSelectionRange.start.character,
+              label: runnable.label,
+            },
+          ]
+        : []
+    );
+    if (targets.length === 0) {
+      return `No related tests found for ${filePath}:${position.line + 1}`;
+    }
+
+    return formatNavigationTargets(
+      root,
+      `Found ${targets.length} related test${targets.length === 1 ? "" : "s"} for ${filePath}:${position.line + 1}`,
+      targets
+    );
+  }, "Rust");
+}
+
+export const rustRelatedTestsTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_related_tests",
+  description:
+    "Find the tests that exercise the Rust item at a position (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleRelatedTests(args);
+  },
+};
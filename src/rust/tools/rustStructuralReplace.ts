@@ -0,0 +1,114 @@
+import { z } from "zod";
+import type { WorkspaceEdit } from "vscode-languageserver-types";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  applyWorkspaceEdit,
+  formatWorkspaceEditSummary,
+} from "../../lsp/applyWorkspaceEdit.ts";
+import {
+  prepareFileContext,
+  resolvePositionOrThrow,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  query: z
+    .string()
+    .describe("SSR rule `pattern ==>> replacement`, e.g. `$a.add($b) ==>> $a.plus($b)`"),
+  filePath: z
+    .string()
+    .describe("File whose scope resolves paths in the rule (relative to root)"),
+  line: commonSchemas.line
+    .describe("Line in filePath whose scope resolves paths in the rule (defaults to the first line)")
+    .optional(),
+  dryRun: z
+    .boolean()
+    .optional()
+    .describe("Preview the replacement as a unified diff without modifying any files"),
+  ...waitForIdleShape,
+});
+
+type StructuralReplaceRequest = z.infer<typeof schema>;
+
+// Params of rust-analyzer's experimental/ssr
+interface SsrParams {
+  query: string;
+  parseOnly: boolean;
+  textDocument: { uri: string };
+  position: { line: number; character: number };
+  // Restrict matching to these ranges of textDocument; empty searches the workspace
+  selections: { start: { line: number; character: number }; end: { line: number; character: number } }[];
+}
+
+async function handleStructuralReplace({
+  root,
+  query,
+  filePath,
+  line,
+  dryRun,
+  waitForIdle,
+}: StructuralReplaceRequest): Promise<string> {
+  if (!query.includes("==>>")) {
+    throw new Error(
+      "The query must be a rule of the form `pattern ==>> replacement`"
+    );
+  }
+
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+  const position =
+    line !== undefined
+      ? resolvePositionOrThrow(content, line, undefined, filePath)
+      : { line: 0, character: 0 };
+
+  return withLSPDocument(fileUri, content, async () => {
+    const client = getLSPClient()!;
+    // Matching resolves paths, so results are only complete once the crate graph is loaded
+    await waitForIdleIfRequested(waitForIdle);
+
+    const params: SsrParams = {
+      query,
+      parseOnly: false,
+      textDocument: { uri: fileUri },
+      position,
+      selections: [],
+    };
+    const edit = await client.sendRequest<WorkspaceEdit | null>("experimental/ssr", params);
+    if (!edit) {
+      return `No matches found for \`${query}\``;
+    }
+
+    // Same edit application as lsmcp_rename_symbol
+    const result = applyWorkspaceEdit(edit, { dryRun, root });
+    const editCount = result.files.reduce((sum, file) => sum + file.edits.length, 0);
+    if (editCount === 0) {
+      return `No matches found for \`${query}\``;
+    }
+    const files = result.files.length;
+    const lines = [
+      dryRun
+        ? `Dry run: the replacement would make ${editCount} edit(s) in ${files} file(s). No files were modified.`
+        : `Replaced ${editCount} match(es) in ${files} file(s)`,
+      "",
+      formatWorkspaceEditSummary(result, root),
+    ];
+    if (result.diff) {
+      lines.push("", result.diff);
+    }
+    return lines.join("\n");
+  }, "Rust");
+}
+
+export const rustStructuralReplaceTool: ToolDef<typeof schema> = {
+  name: "lsmcp_structural_replace",
+  description:
+    "Structural search and replace across a Rust workspace with rust-analyzer SSR rules (e.g. `$a.add($b) ==>> $a.plus($b)`), with a dry-run diff mode",
+  schema,
+  execute: async (args) => {
+    return handleStructuralReplace(args);
+  },
+};
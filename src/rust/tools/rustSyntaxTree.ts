@@ -0,0 +1,62 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  resolveLineOrThrow,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  startLine: z
+    .union([z.number(), z.string()])
+    .describe("First line (1-based) or string to match; the whole file when omitted")
+    .optional(),
+  endLine: z
+    .union([z.number(), z.string()])
+    .describe("Last line (1-based) or string to match (defaults to startLine)")
+    .optional(),
+});
+
+async function handleSyntaxTree({
+  root,
+  filePath,
+  startLine,
+  endLine,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+  const lines = content.split("\n");
+
+  let range;
+  if (startLine !== undefined) {
+    const start = resolveLineOrThrow(content, startLine, filePath);
+    const end =
+      endLine !== undefined ? resolveLineOrThrow(content, endLine, filePath) : start;
+    range = {
+      start: { line: start, character: 0 },
+      end: { line: end, character: lines[end]?.length ?? 0 },
+    };
+  }
+
+  return withLSPDocument(fileUri, content, async () => {
+    const client = getLSPClient()!;
+    const tree = await client.sendRequest<string>("rust-analyzer/syntaxTree", {
+      textDocument: { uri: fileUri },
+      range,
+    });
+    return tree || `No syntax tree available for ${filePath}`;
+  }, "Rust");
+}
+
+export const rustSyntaxTreeTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_syntax_tree",
+  description:
+    "Show the rust-analyzer syntax tree of a Rust file or line range",
+  schema,
+  execute: async (args) => {
+    return handleSyntaxTree(args);
+  },
+};
@@ -0,0 +1,54 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import {
+  prepareFileContext,
+  resolveTargetPosition,
+  waitForIdleIfRequested,
+  waitForIdleShape,
+  withLSPDocument,
+} from "../../lsp/tools/lspCommon.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+  line: commonSchemas.line,
+  target: z
+    .string()
+    .describe("Text inside the function to show the HIR for")
+    .optional(),
+  ...waitForIdleShape,
+});
+
+async function handleViewHir({
+  root,
+  filePath,
+  line,
+  target,
+  waitForIdle,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const position = await resolveTargetPosition(fileUri, content, line, target, filePath);
+    const client = getLSPClient()!;
+    await waitForIdleIfRequested(waitForIdle);
+
+    const hir = await client.sendRequest<string>("rust-analyzer/viewHir", {
+      textDocument: { uri: fileUri },
+      position,
+    });
+    return hir || `No function found at ${filePath}:${position.line + 1}`;
+  }, "Rust");
+}
+
+export const rustViewHirTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_view_hir",
+  description:
+    "Show the HIR (desugared, name-resolved body) of the Rust function at a position (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleViewHir(args);
+  },
+};
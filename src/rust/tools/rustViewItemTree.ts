@@ -0,0 +1,35 @@
+import { z } from "zod";
+import type { ToolDef } from "../../mcp/_mcplib.ts";
+import { getLSPClient } from "../../lsp/lspClient.ts";
+import { commonSchemas } from "../../common/schemas.ts";
+import { prepareFileContext, withLSPDocument } from "../../lsp/tools/lspCommon.ts";
+
+const schema = z.object({
+  root: commonSchemas.root,
+  filePath: commonSchemas.filePath,
+});
+
+async function handleViewItemTree({
+  root,
+  filePath,
+}: z.infer<typeof schema>): Promise<string> {
+  const { fileUri, content } = await prepareFileContext(root, filePath);
+
+  return withLSPDocument(fileUri, content, async () => {
+    const client = getLSPClient()!;
+    const itemTree = await client.sendRequest<string>("rust-analyzer/viewItemTree", {
+      textDocument: { uri: fileUri },
+    });
+    return itemTree || `No item tree available for ${filePath}`;
+  }, "Rust");
+}
+
+export const rustViewItemTreeTool: ToolDef<typeof schema> = {
+  name: "lsmcp_rust_view_item_tree",
+  description:
+    "Show the item tree (items and their signatures, with macros expanded) of a Rust file (rust-analyzer)",
+  schema,
+  execute: async (args) => {
+    return handleViewItemTree(args);
+  },
+};
@@ -0,0 +1,67 @@
+import { describe, it, expect } from "vitest";
+import { createUnifiedDiff } from "./createUnifiedDiff.ts";
+
+describe("createUnifiedDiff", () => {
+  it("should return an empty string for equal contents", () => {
+    expect(createUnifiedDiff("a.ts", "a.ts", "x\n", "x\n")).toBe("");
+  });
+
+  it("should create a hunk with surrounding context", () => {
+    const oldContent = ["1", "2", "3", "4", "5", "6", "7", "8"].join("\n");
+    const newContent = ["1", "2", "3", "4", "five", "6", "7", "8"].join("\n");
+
+    expect(createUnifiedDiff("a.ts", "a.ts", oldContent, newContent)).toBe(
+      [
+        "--- a/a.ts",
+        "+++ b/a.ts",
+        "@@ -2,7 +2,7 @@",
+        " 2",
+        " 3",
+        " 4",
+        "-5",
+        "+five",
+        " 6",
+        " 7",
+        " 8",
+      ].join("\n")
+    );
+  });
+
+  it("should split distant changes into separate hunks", () => {
+    const lines = Array.from({ length: 20 }, (_, i) => `line ${i + 1}`);
+    const changed = [...lines];
+    changed[1] = "changed 2";
+    changed[17] = "changed 18";
+
+    const diff = createUnifiedDiff("a.ts", "a.ts", lines.join("\n"), changed.join("\n"));
+
+    expect(diff).toContain("@@ -1,5 +1,5 @@");
+    expect(diff).toContain("@@ -15,6 +15,6 @@");
+  });
+
+  it("should diff created files against /dev/null", () => {
+    const diff = createUnifiedDiff("/dev/null", "src/new.rs", "", "fn a() {}\n");
+
+    expect(diff).toBe(
+      ["--- /dev/null", "+++ b/src/new.rs", "@@ -0,0 +1,1 @@", "+fn a() {}"].join("\n")
+    );
+  });
+
+  it("should handle insertions in the middle", () => {
+    const diff = createUnifiedDiff("a.ts", "a.ts", "a\nc\n", "a\nb\nc\n");
+
+    expect(diff).toContain("@@ -1,2 +1,3 @@");
+    expect(diff).toContain(" a\n+b\n c");
+  });
+
+  it("should diff large files with changes at both ends", () => {
+    const lines = Array.from({ length: 50000 }, (_, i) => `line ${i + 1}`);
+    const changed = ["header", ...lines.slice(1, -1), "footer"];
+
+    const diff = createUnifiedDiff("a.ts", "a.ts", lines.join("\n"), changed.join("\n"));
+
+    expect(diff).toContain("@@ -1,4 +1,4 @@\n-line 1\n+header");
+    expect(diff).toContain("@@ -49997,4 +49997,4 @@");
+    expect(diff).toContain("-line 50000\n+footer");
+  });
+});
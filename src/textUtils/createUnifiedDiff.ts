@@ -0,0 +1,191 @@
+type DiffOp = { type: " " | "-" | "+"; text: string };
+
+function splitLines(content: string): string[] {
+  if (content === "") {
+    return [];
+  }
+  const lines = content.split("\n");
+  // A trailing newline does not start another line
+  if (lines[lines.length - 1] === "") {
+    lines.pop();
+  }
+  return lines;
+}
+
+/**
+ * Compute line operations with Myers' linear-space diff: split at the middle
+ * snake of a shortest edit script and recurse on both halves, trimming the
+ * common prefix and suffix at each level
+ */
+function diffLines(a: string[], b: string[]): DiffOp[] {
+  const ops: DiffOp[] = [];
+  diffRange(a, 0, a.length, b, 0, b.length, ops);
+  return ops;
+}
+
+function diffRange(
+  a: string[],
+  aStart: number,
+  aEnd: number,
+  b: string[],
+  bStart: number,
+  bEnd: number,
+  ops: DiffOp[]
+): void {
+  while (aStart < aEnd && bStart < bEnd && a[aStart] === b[bStart]) {
+    ops.push({ type: " ", text: a[aStart] });
+    aStart++;
+    bStart++;
+  }
+  let suffix = 0;
+  while (
+    suffix < aEnd - aStart &&
+    suffix < bEnd - bStart &&
+    a[aEnd - 1 - suffix] === b[bEnd - 1 - suffix]
+  ) {
+    suffix++;
+  }
+  aEnd -= suffix;
+  bEnd -= suffix;
+
+  if (aStart === aEnd) {
+    for (let j = bStart; j < bEnd; j++) {
+      ops.push({ type: "+", text: b[j] });
+    }
+  } else if (bStart === bEnd) {
+    for (let i = aStart; i < aEnd; i++) {
+      ops.push({ type: "-", text: a[i] });
+    }
+  } else {
+    const [x, y, u, v] = middleSnake(a, aStart, aEnd, b, bStart, bEnd);
+    diffRange(a, aStart, x, b, bStart, y, ops);
+    for (let i = x; i < u; i++) {
+      ops.push({ type: " ", text: a[i] });
+    }
+    diffRange(a, u, aEnd, b, v, bEnd, ops);
+  }
+
+  for (let i = aEnd; i < aEnd + suffix; i++) {
+    ops.push({ type: " ", text: a[i] });
+  }
+}
+
+/**
+ * Find the middle snake of a shortest edit script by searching forward from the
+ * start and backward from the end until the paths overlap
+ * @returns The start and end points of the snake as [x, y, u, v]
+ */
+function middleSnake(
+  a: string[],
+  aStart: number,
+  aEnd: number,
+  b: string[],
+  bStart: number,
+  bEnd: number
+): [number, number, number, number] {
+  const n = aEnd - aStart;
+  const m = bEnd - bStart;
+  const delta = n - m;
+  const odd = (delta & 1) !== 0;
+  const max = Math.ceil((n + m) / 2);
+  const offset = max + 1;
+  // Furthest x reached on each diagonal k = x - y, counted from the start (forward)
+  // or from the end (backward)
+  const forward = new Int32Array(2 * max + 3);
+  const backward = new Int32Array(2 * max + 3);
+
+  for (let d = 0; d <= max; d++) {
+    for (let k = -d; k <= d; k += 2) {
+      const down = k === -d || (k !== d && forward[offset + k - 1] < forward[offset + k + 1]);
+      const x0 = down ? forward[offset + k + 1] : forward[offset + k - 1] + 1;
+      const y0 = x0 - k;
+      let x = x0;
+      let y = y0;
+      while (x < n && y < m && a[aStart + x] === b[bStart + y]) {
+        x++;
+        y++;
+      }
+      forward[offset + k] = x;
+      const c = delta - k;
+      if (odd && c >= -(d - 1) && c <= d - 1 && x + backward[offset + c] >= n) {
+        return [aStart + x0, bStart + y0, aStart + x, bStart + y];
+      }
+    }
+
+    for (let k = -d; k <= d; k += 2) {
+      const down = k === -d || (k !== d && backward[offset + k - 1] < backward[offset + k + 1]);
+      const x0 = down ? backward[offset + k + 1] : backward[offset + k - 1] + 1;
+      const y0 = x0 - k;
+      let x = x0;
+      let y = y0;
+      while (x < n && y < m && a[aEnd - 1 - x] === b[bEnd - 1 - y]) {
+        x++;
+        y++;
+      }
+      backward[offset + k] = x;
+      const c = delta - k;
+      if (!odd && c >= -d && c <= d && x + forward[offset + c] >= n) {
+        return [aStart + n - x, bStart + m - y, aStart + n - x0, bStart + m - y0];
+      }
+    }
+  }
+
+  throw new Error("Unreachable: no middle snake found");
+}
+
+/**
+ * Create a unified diff between two versions of a file.
+ * Use "/dev/null" as a path for created or deleted files.
+ * @returns The diff text, or an empty string if the contents are equal
+ */
+export function createUnifiedDiff(
+  oldPath: string,
+  newPath: string,
+  oldContent: string,
+  newContent: string,
+  context: number = 3
+): string {
+  if (oldContent === newContent) {
+    return "";
+  }
+
+  const ops = diffLines(splitLines(oldContent), splitLines(newContent));
+  const changes = ops
+    .map((op, index) => (op.type === " " ? -1 : index))
+    .filter((index) => index >= 0);
+
+  const oldPrefix = oldPath === "/dev/null" ? "" : "a/";
+  const newPrefix = newPath === "/dev/null" ? "" : "b/";
+  const output = [`--- ${oldPrefix}${oldPath}`, `+++ ${newPrefix}${newPath}`];
+
+  let groupStart = 0;
+  while (groupStart < changes.length) {
+    // Merge changes whose context windows overlap into one hunk
+    let groupEnd = groupStart;
+    while (
+      groupEnd + 1 < changes.length &&
+      changes[groupEnd + 1] - changes[groupEnd] <= context * 2 + 1
+    ) {
+      groupEnd++;
+    }
+
+    const start = Math.max(0, changes[groupStart] - context);
+    const end = Math.min(ops.length, changes[groupEnd] + context + 1);
+    const before = ops.slice(0, start);
+    const hunk = ops.slice(start, end);
+
+    const oldCount = hunk.filter((op) => op.type !== "+").length;
+    const newCount = hunk.filter((op) => op.type !== "-").length;
+    const oldStart = before.filter((op) => op.type !== "+").length + (oldCount > 0 ? 1 : 0);
+    const newStart = before.filter((op) => op.type !== "-").length + (newCount > 0 ? 1 : 0);
+
+    output.push(`@@ -${oldStart},${oldCount} +${newStart},${newCount} @@`);
+    for (const op of hunk) {
+      output.push(`${op.type}${op.text}`);
+    }
+
+    groupStart = groupEnd + 1;
+  }
+
+  return output.join("\n");
+}
@@ -1,18 +1,24 @@
 import { findSymbolOccurrences } from "./findSymbolOccurrences.ts";
+import { restrictToSymbolTokens, type SemanticToken } from "./semanticTokens.ts";
 
 /**
  * Finds the position of a symbol within a line
  * @param lineText The text of the line
  * @param symbolName The symbol to find
  * @param symbolIndex Optional index if symbol appears multiple times (0-based)
+ * @param lineTokens Optional semantic tokens of the line, to skip matches inside strings and comments
  * @returns Character index or error message
  */
 export function findSymbolInLine(
   lineText: string,
   symbolName: string,
-  symbolIndex = 0
+  symbolIndex = 0,
+  lineTokens?: SemanticToken[]
 ): { characterIndex: number } | { error: string } {
-  const occurrences = findSymbolOccurrences(lineText, symbolName);
+  let occurrences = findSymbolOccurrences(lineText, symbolName);
+  if (lineTokens) {
+    occurrences = restrictToSymbolTokens(occurrences, symbolName.length, lineTokens);
+  }
 
   if (occurrences.length === 0) {
     return { error: `Symbol "${symbolName}" not found` };
@@ -46,6 +52,17 @@ if (import.meta.vitest) {
       expect(result).toEqual({ error: 'Symbol "foo" not found' });
     });
 
+    it("should skip matches that are not symbol tokens", () => {
+      const lineText = 'println!("value = {}", calc.value);';
+      const result = findSymbolInLine(lineText, "value", 0, [
+        { line: 0, character: 0, length: 7, tokenType: "macro", tokenModifiers: [] },
+        { line: 0, character: 9, length: 12, tokenType: "string", tokenModifiers: [] },
+        { line: 0, character: 23, length: 4, tokenType: "variable", tokenModifiers: [] },
+        { line: 0, character: 28, length: 5, tokenType: "property", tokenModifiers: [] },
+      ]);
+      expect(result).toEqual({ characterIndex: 28 });
+    });
+
     it("should return error for invalid index", () => {
       const result = findSymbolInLine("const foo = 1;", "foo", 1);
       expect(result).toEqual({ 
@@ -1,15 +1,29 @@
 import { findTextInFile } from "./findTextInFile.ts";
+import { findSymbolOccurrences } from "./findSymbolOccurrences.ts";
+import { isSymbolToken, type SemanticToken } from "./semanticTokens.ts";
 
 /**
  * Finds the first occurrence of target text across all lines
  * @param lines Array of file lines
  * @param target Text to find
+ * @param tokens Optional semantic tokens of the file; the first occurrence that is a symbol token is preferred
  * @returns Line index and character position or error
  */
 export function findTargetInFile(
   lines: string[],
-  target: string
+  target: string,
+  tokens?: SemanticToken[]
 ): { lineIndex: number; characterIndex: number } | { error: string } {
+  if (tokens) {
+    for (const token of tokens) {
+      if (token.length !== target.length || !isSymbolToken(token)) continue;
+      const occurrences = findSymbolOccurrences(lines[token.line] ?? "", target);
+      if (occurrences.includes(token.character)) {
+        return { lineIndex: token.line, characterIndex: token.character };
+      }
+    }
+  }
+
   const fullText = lines.join("\n");
   const result = findTextInFile(fullText, target);
 
@@ -41,6 +55,16 @@ if (import.meta.vitest) {
       expect(result).toEqual({ lineIndex: 0, characterIndex: 0 });
     });
 
+    it("should prefer symbol tokens over matches in comments", () => {
+      const lines = ["// reset value", "self.value = 0;"];
+
+      const result = findTargetInFile(lines, "value", [
+        { line: 0, character: 0, length: 14, tokenType: "comment", tokenModifiers: [] },
+        { line: 1, character: 5, length: 5, tokenType: "property", tokenModifiers: [] },
+      ]);
+      expect(result).toEqual({ lineIndex: 1, characterIndex: 5 });
+    });
+
     it("should return error if target not found", () => {
       const lines = ["const foo = 1;", "const bar = 2;"];
       
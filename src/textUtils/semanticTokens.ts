@@ -0,0 +1,153 @@
+/**
+ * Semantic token decoding and symbol classification
+ */
+
+export interface SemanticTokensLegend {
+  tokenTypes: string[];
+  tokenModifiers: string[];
+}
+
+export interface SemanticToken {
+  // 0-based position
+  line: number;
+  character: number;
+  length: number;
+  tokenType: string;
+  tokenModifiers: string[];
+}
+
+// Token types that name a symbol (standard LSP types plus rust-analyzer's),
+// as opposed to strings, comments, keywords, literals and punctuation
+const SYMBOL_TOKEN_TYPES = new Set([
+  "namespace",
+  "type",
+  "class",
+  "enum",
+  "interface",
+  "struct",
+  "typeParameter",
+  "parameter",
+  "variable",
+  "property",
+  "enumMember",
+  "event",
+  "function",
+  "method",
+  "macro",
+  "label",
+  "decorator",
+  "member",
+  // rust-analyzer
+  "builtinType",
+  "const",
+  "constParameter",
+  "derive",
+  "deriveHelper",
+  "generic",
+  "lifetime",
+  "selfKeyword",
+  "selfTypeKeyword",
+  "static",
+  "toolModule",
+  "trait",
+  "typeAlias",
+  "union",
+  "unresolvedReference",
+]);
+
+/**
+ * Decode the relative `data` array of a semantic tokens response
+ */
+export function decodeSemanticTokens(
+  data: number[],
+  legend: SemanticTokensLegend
+): SemanticToken[] {
+  const tokens: SemanticToken[] = [];
+  let line = 0;
+  let character = 0;
+
+  for (let i = 0; i + 4 < data.length; i += 5) {
+    const [deltaLine, deltaStart, length, typeIndex, modifierBits] = data.slice(i, i + 5);
+    line += deltaLine;
+    character = deltaLine === 0 ? character + deltaStart : deltaStart;
+
+    const tokenModifiers = legend.tokenModifiers.filter(
+      (_, bit) => (modifierBits & (1 << bit)) !== 0
+    );
+    tokens.push({
+      line,
+      character,
+      length,
+      tokenType: legend.tokenTypes[typeIndex] ?? "unknown",
+      tokenModifiers,
+    });
+  }
+
+  return tokens;
+}
+
+export function isSymbolToken(token: SemanticToken): boolean {
+  return SYMBOL_TOKEN_TYPES.has(token.tokenType);
+}
+
+/**
+ * Keep the occurrences that start a symbol token of the same length.
+ * Falls back to all occurrences when none does (e.g. the target is not an
+ * identifier, or the server has not classified the line yet).
+ */
+export function restrictToSymbolTokens(
+  occurrences: number[],
+  length: number,
+  lineTokens: SemanticToken[]
+): number[] {
+  const restricted = occurrences.filter((character) =>
+    lineTokens.some(
+      (token) =>
+        token.character === character && token.length === length && isSymbolToken(token)
+    )
+  );
+  return restricted.length > 0 ? restricted : occurrences;
+}
+
+if (import.meta.vitest) {
+  const { describe, it, expect } = import.meta.vitest;
+
+  const legend: SemanticTokensLegend = {
+    tokenTypes: ["variable", "property", "string", "comment"],
+    tokenModifiers: ["declaration", "readonly"],
+  };
+
+  describe("decodeSemanticTokens", () => {
+    it("should decode relative positions and modifier bits", () => {
+      // `let value = "value"; // value` followed by `self.value` on the next line
+      const data = [0, 4, 5, 0, 1, 0, 8, 7, 2, 0, 0, 9, 8, 3, 0, 1, 9, 5, 1, 2];
+
+      expect(decodeSemanticTokens(data, legend)).toEqual([
+        { line: 0, character: 4, length: 5, tokenType: "variable", tokenModifiers: ["declaration"] },
+        { line: 0, character: 12, length: 7, tokenType: "string", tokenModifiers: [] },
+        { line: 0, character: 21, length: 8, tokenType: "comment", tokenModifiers: [] },
+        { line: 1, character: 9, length: 5, tokenType: "property", tokenModifiers: ["readonly"] },
+      ]);
+    });
+  });
+
+  describe("restrictToSymbolTokens", () => {
+    const token = (character: number, length: number, tokenType: string): SemanticToken => ({
+      line: 0,
+      character,
+      length,
+      tokenType,
+      tokenModifiers: [],
+    });
+
+    it("should skip occurrences inside strings and comments", () => {
+      // assert_eq!("value", self.value); // value
+      const tokens = [token(11, 7, "string"), token(25, 5, "property"), token(33, 8, "comment")];
+      expect(restrictToSymbolTokens([12, 25, 36], 5, tokens)).toEqual([25]);
+    });
+
+    it("should fall back to all occurrences when none is a symbol", () => {
+      expect(restrictToSymbolTokens([0, 10], 8, [token(0, 7, "macro")])).toEqual([0, 10]);
+    });
+  });
+}
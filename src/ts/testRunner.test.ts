@@ -0,0 +1,107 @@
+import { describe, it, expect } from "vitest";
+import { join } from "path";
+import {
+  buildTestCommand,
+  detectTestFramework,
+  discoverTestBlocks,
+  parseJestJsonReport,
+} from "./testRunner.ts";
+
+describe("testRunner", () => {
+  const source = [
+    'import { describe, it, expect } from "vitest";',
+    "",
+    'describe("math", () => {',
+    '  it("adds", () => {',
+    "    expect(1 + 1).toBe(2);",
+    "  });",
+    "",
+    '  describe("division", () => {',
+    '    it.skip("by zero", () => {});',
+    "  });",
+    "});",
+    "",
+    'test("top level", () => {',
+    "  expect(true).toBe(true);",
+    "});",
+  ].join("\n");
+
+  it("should discover nested test blocks with full names and line ranges", () => {
+    expect(discoverTestBlocks(source)).toEqual([
+      { kind: "describe", name: "math", startLine: 3, endLine: 11 },
+      { kind: "test", name: "math adds", startLine: 4, endLine: 6 },
+      { kind: "describe", name: "math division", startLine: 8, endLine: 10 },
+      { kind: "test", name: "math division by zero", startLine: 9, endLine: 9 },
+      { kind: "test", name: "top level", startLine: 13, endLine: 15 },
+    ]);
+  });
+
+  it("should filter vitest and jest runs by escaped name pattern", () => {
+    const [mathDescribe, adds] = discoverTestBlocks(source);
+
+    expect(
+      buildTestCommand("vitest", "/work", "/work/src/math.test.ts", mathDescribe)
+    ).toEqual({
+      command: "npx",
+      args: [
+        "vitest",
+        "run",
+        "src/math.test.ts",
+        "--reporter=json",
+        "--testNamePattern=^math( |$)",
+      ],
+      cwd: "/work",
+    });
+    expect(
+      buildTestCommand("jest", "/work", "/work/src/math.test.ts", {
+        ...adds,
+        name: "math adds (1 + 1)",
+      }).args
+    ).toEqual([
+      "jest",
+      "src/math.test.ts",
+      "--json",
+      "--testLocationInResults",
+      "--testNamePattern=^math adds \\(1 \\+ 1\\)$",
+    ]);
+  });
+
+  it("should detect vitest from package.json", () => {
+    const detected = detectTestFramework(join(process.cwd(), "src/ts/testRunner.ts"));
+    expect(detected).toEqual({ framework: "vitest", packageDir: process.cwd() });
+  });
+
+  it("should map failures to the test file stack frame", () => {
+    const report = JSON.stringify({
+      numFailedTests: 1,
+      testResults: [
+        {
+          name: "/work/src/math.test.ts",
+          assertionResults: [
+            { fullName: "math adds", status: "passed", failureMessages: [] },
+            {
+              fullName: "math subtracts",
+              status: "failed",
+              failureMessages: [
+                "AssertionError: expected 1 to be 2 // Object.is equality\n    at /work/src/math.test.ts:8:19\n    at node_modules/vitest/dist/index.js:1:1",
+              ],
+              location: { line: 7, column: 3 },
+            },
+            { fullName: "math division by zero", status: "skipped" },
+          ],
+        },
+      ],
+    });
+
+    expect(parseJestJsonReport(`RUN v1.0.0\n${report}`)).toEqual([
+      { name: "math adds", status: "passed" },
+      {
+        name: "math subtracts",
+        status: "failed",
+        message: "AssertionError: expected 1 to be 2 // Object.is equality",
+        location: { filePath: "/work/src/math.test.ts", line: 8 },
+      },
+      { name: "math division by zero", status: "ignored" },
+    ]);
+  });
+});
@@ -0,0 +1,189 @@
+/**
+ * Test discovery and result parsing for vitest / jest projects
+ */
+
+import { existsSync, readFileSync } from "fs";
+import path from "path";
+import type { TestCaseResult } from "../common/testResults.ts";
+
+export type TestFramework = "vitest" | "jest";
+
+export interface TestBlock {
+  kind: "describe" | "test";
+  // Full name as matched by --testNamePattern (describe titles joined by spaces)
+  name: string;
+  // 1-based line range
+  startLine: number;
+  endLine: number;
+}
+
+const TEST_BLOCK =
+  /^(\s*)(describe|it|test)((?:\.(?:only|skip|concurrent|sequential|todo|fails))*)\s*\(\s*(["'`])(.*?)\4/;
+
+/**
+ * Find the test framework of the package containing filePath
+ * @returns The framework and the package directory, or null if none is configured
+ */
+export function detectTestFramework(
+  filePath: string
+): { framework: TestFramework; packageDir: string } | null {
+  let dir = path.dirname(filePath);
+  while (true) {
+    const packageJsonPath = path.join(dir, "package.json");
+    if (existsSync(packageJsonPath)) {
+      try {
+        const pkg = JSON.parse(readFileSync(packageJsonPath, "utf-8"));
+        const deps = { ...pkg.dependencies, ...pkg.devDependencies };
+        if (deps.vitest) {
+          return { framework: "vitest", packageDir: dir };
+        }
+        if (deps.jest) {
+          return { framework: "jest", packageDir: dir };
+        }
+      } catch {
+        // Ignore malformed package.json and keep looking upwards
+      }
+    }
+    const parent = path.dirname(dir);
+    if (parent === dir) {
+      return null;
+    }
+    dir = parent;
+  }
+}
+
+/**
+ * Find describe/it/test blocks in a test file.
+ * Nesting and block ends are derived from indentation, which holds for formatted code.
+ */
+export function discoverTestBlocks(content: string): TestBlock[] {
+  const lines = content.split("\n");
+  const blocks: TestBlock[] = [];
+  const stack: { indent: number; title: string }[] = [];
+
+  for (let i = 0; i < lines.length; i++) {
+    const match = lines[i].match(TEST_BLOCK);
+    if (!match) continue;
+
+    const [, indentText, keyword, , , title] = match;
+    const indent = indentText.length;
+    while (stack.length > 0 && stack[stack.length - 1].indent >= indent) {
+      stack.pop();
+    }
+
+    // The block ends at the first closing line with the same indentation
+    let endLine = i;
+    for (let j = i + 1; j < lines.length; j++) {
+      const line = lines[j];
+      const lineIndent = line.length - line.trimStart().length;
+      if (line.trim() === "") continue;
+      if (lineIndent < indent) break;
+      if (lineIndent === indent && line.trimStart().startsWith("}")) {
+        endLine = j;
+        break;
+      }
+    }
+
+    const kind = keyword === "describe" ? "describe" : "test";
+    blocks.push({
+      kind,
+      name: [...stack.map((entry) => entry.title), title].join(" "),
+      startLine: i + 1,
+      endLine: endLine + 1,
+    });
+    if (kind === "describe") {
+      stack.push({ indent, title });
+    }
+  }
+
+  return blocks;
+}
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
+
+/**
+ * Command line that runs a test file, optionally filtered to one block
+ */
+export function buildTestCommand(
+  framework: TestFramework,
+  packageDir: string,
+  filePath: string,
+  block?: TestBlock
+): { command: string; args: string[]; cwd: string } {
+  const relativePath = path.relative(packageDir, filePath);
+  const args =
+    framework === "vitest"
+      ? ["vitest", "run", relativePath, "--reporter=json"]
+      : ["jest", relativePath, "--json", "--testLocationInResults"];
+  if (block) {
+    // A describe matches every test below it, a test only itself
+    const pattern = escapeRegExp(block.name);
+    args.push(
+      `--testNamePattern=^${pattern}${block.kind === "describe" ? "( |$)" : "$"}`
+    );
+  }
+  return { command: "npx", args, cwd: packageDir };
+}
+
+interface JestAssertionResult {
+  fullName: string;
+  status: string;
+  failureMessages?: string[] | null;
+  location?: { line: number; column: number } | null;
+}
+
+interface JestTestResult {
+  name: string;
+  assertionResults: JestAssertionResult[];
+  message?: string;
+}
+
+/**
+ * Parse the Jest-compatible JSON report written by `vitest --reporter=json` and `jest --json`.
+ * Failures are mapped to the first stack frame inside the test file.
+ */
+export function parseJestJsonReport(output: string): TestCaseResult[] {
+  const start = output.indexOf("{");
+  const end = output.lastIndexOf("}");
+  if (start === -1 || end < start) {
+    return [];
+  }
+
+  let report: { testResults?: JestTestResult[] };
+  try {
+    report = JSON.parse(output.slice(start, end + 1));
+  } catch {
+    return [];
+  }
+
+  const results: TestCaseResult[] = [];
+  for (const file of report.testResults ?? []) {
+    for (const assertion of file.assertionResults) {
+      const status =
+        assertion.status === "passed"
+          ? "passed"
+          : assertion.status === "failed"
+            ? "failed"
+            : "ignored";
+      const result: TestCaseResult = { name: assertion.fullName, status };
+
+      if (status === "failed") {
+        const message = (assertion.failureMessages ?? []).join("\n");
+        result.message = message.split("\n    at ")[0].trim();
+        const frame = message.match(
+          new RegExp(`${escapeRegExp(file.name)}:(\\d+):\\d+`)
+        );
+        if (frame) {
+          result.location = { filePath: file.name, line: parseInt(frame[1], 10) };
+        }
+      }
+      if (!result.location && assertion.location) {
+        result.location = { filePath: file.name, line: assertion.location.line };
+      }
+      results.push(result);
+    }
+  }
+  return results;
+}
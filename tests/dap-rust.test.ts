@@ -0,0 +1,110 @@
+import { describe, it, expect, beforeEach, afterEach } from "vitest";
+import { Client } from "@modelcontextprotocol/sdk/client/index.js";
+import { StdioClientTransport } from "@modelcontextprotocol/sdk/client/stdio.js";
+import path from "path";
+import { fileURLToPath } from "url";
+import { isAdapterAvailable } from "../src/dap/adapterResolver.ts";
+
+const __dirname = path.dirname(fileURLToPath(import.meta.url));
+const RUST_PROJECT = path.join(__dirname, "../examples/rust-project");
+
+describe.skipIf(!isAdapterAvailable("lldb"))("DAP lldb adapter with Rust", { timeout: 120000 }, () => {
+  let client: Client;
+  let transport: StdioClientTransport;
+
+  const callText = async (name: string, args: Record<string, unknown>) => {
+    const result = await client.callTool({ name, arguments: args });
+    return (result.content as any)[0]?.text as string;
+  };
+
+  const waitForStopped = async (sessionId: string) => {
+    for (let i = 0; i < 100; i++) {
+      const info = await callText("debug_get_session_info", { sessionId });
+      if (info.includes("State: stopped")) {
+        return;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 100));
+    }
+    throw new Error(`Session ${sessionId} did not stop`);
+  };
+
+  beforeEach(async () => {
+    transport = new StdioClientTransport({
+      command: "node",
+      args: [path.join(__dirname, "../dist/dap-mcp.js")],
+    });
+    client = new Client({ name: "test-client", version: "1.0.0" }, { capabilities: {} });
+    await client.connect(transport);
+  });
+
+  afterEach(async () => {
+    await client.close();
+  });
+
+  it("should build the crate and inspect Calculator.value at a breakpoint", async () => {
+    const sessionId = "rust-calculator";
+    const mainRs = path.join(RUST_PROJECT, "src/main.rs");
+
+    const launchResult = await callText("debug_launch", {
+      sessionId,
+      adapter: "lldb",
+      program: RUST_PROJECT,
+      stopOnEntry: true,
+    });
+    expect(launchResult).toContain("launched");
+    expect(launchResult).toContain(path.join("target", "debug", "rust-project"));
+    expect(launchResult).toContain("Built with cargo build");
+
+    await waitForStopped(sessionId);
+
+    // println!("Calculator result: ...") runs after add(10.0).subtract(3.0)
+    await callText("debug_set_breakpoints", {
+      sessionId,
+      source: mainRs,
+      lines: [10],
+    });
+    await callText("debug_continue", { sessionId });
+    await waitForStopped(sessionId);
+
+    const stackTrace = await callText("debug_get_stack_trace", { sessionId });
+    expect(stackTrace).toContain("main.rs");
+
+    const value = await callText("debug_evaluate", {
+      sessionId,
+      expression: "calc.value",
+      context: "watch",
+    });
+    expect(value).toContain("calc.value = 7");
+
+    await callText("debug_disconnect", { sessionId });
+  });
+
+  it("should debug a single test and stop inside the test body", async () => {
+    const sessionId = "rust-test-calculator";
+    const libRs = path.join(RUST_PROJECT, "src/lib.rs");
+
+    // assert_eq!(calc.get_value(), 3.0) in tests::test_calculator
+    const launchResult = await callText("debug_launch_test", {
+      sessionId,
+      program: RUST_PROJECT,
+      testName: "test_calculator",
+      breakpoints: [{ source: libRs, line: 43 }],
+    });
+    expect(launchResult).toContain("launched");
+    expect(launchResult).toContain("Test: tests::test_calculator");
+
+    await waitForStopped(sessionId);
+
+    const stackTrace = await callText("debug_get_stack_trace", { sessionId });
+    expect(stackTrace).toContain("test_calculator");
+
+    const value = await callText("debug_evaluate", {
+      sessionId,
+      expression: "calc.value",
+      context: "watch",
+    });
+    expect(value).toContain("calc.value = 3");
+
+    await callText("debug_disconnect", { sessionId });
+  });
+});
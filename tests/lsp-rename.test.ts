@@ -148,6 +148,36 @@ describe("lsp rename symbol", () => {
     expect(actualContent.trim()).toBe(expectedContent.trim());
   });
 
+  it("should preview a rename as a unified diff in dry-run mode", async () => {
+    if (!process.env.LSP_COMMAND) {
+      return;
+    }
+
+    const inputFile = path.join(FIXTURES_DIR, "function.input.ts");
+    const testFile = path.join(tmpDir, "function-dry-run.ts");
+    await fs.copyFile(inputFile, testFile);
+
+    const result = await lspRenameSymbolTool.execute({
+      root: tmpDir,
+      filePath: "function-dry-run.ts",
+      line: 1,
+      target: "foo",
+      newName: "bar",
+      dryRun: true,
+    });
+
+    expect(result).toContain("Dry run");
+    expect(result).toContain("--- a/function-dry-run.ts");
+    expect(result).toContain("+++ b/function-dry-run.ts");
+    expect(result).toMatch(/^-function foo/m);
+    expect(result).toMatch(/^\+function bar/m);
+
+    // The file is left untouched
+    const actualContent = await fs.readFile(testFile, "utf-8");
+    const inputContent = await fs.readFile(inputFile, "utf-8");
+    expect(actualContent).toBe(inputContent);
+  });
+
   it("should rename without specifying line number", async () => {
     if (!process.env.LSP_COMMAND) {
       return;
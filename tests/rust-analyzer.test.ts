@@ -0,0 +1,418 @@
+import { describe, it, expect, beforeAll, afterAll } from "vitest";
+import { spawn, ChildProcess } from "child_process";
+import path from "path";
+import { existsSync, readFileSync } from "fs";
+import {
+  initialize as initializeLSPClient,
+  shutdown as shutdownLSPClient,
+} from "../src/lsp/lspClient.ts";
+import { lspGetDocumentSymbolsTool } from "../src/lsp/tools/lspGetDocumentSymbols.ts";
+import { lspExtractVariableTool } from "../src/lsp/tools/lspExtractVariable.ts";
+import { lspFindImplementationsTool } from "../src/lsp/tools/lspFindImplementations.ts";
+import { lspGetCallHierarchyTool } from "../src/lsp/tools/lspGetCallHierarchy.ts";
+import { lspGetDiagnosticsTool } from "../src/lsp/tools/lspGetDiagnostics.ts";
+import { lspGetEnclosingRangeTool } from "../src/lsp/tools/lspGetEnclosingRange.ts";
+import { lspGetHoverTool } from "../src/lsp/tools/lspGetHover.ts";
+import { lspGetInlayHintsTool } from "../src/lsp/tools/lspGetInlayHints.ts";
+import { lspGetTypeDefinitionTool } from "../src/lsp/tools/lspGetTypeDefinition.ts";
+import { lspGetWorkspaceDiagnosticsTool } from "../src/lsp/tools/lspGetWorkspaceDiagnostics.ts";
+import { lspInlineSymbolTool } from "../src/lsp/tools/lspInlineSymbol.ts";
+import { lspMoveFileTool } from "../src/lsp/tools/lspMoveFile.ts";
+import { lspOrganizeImportsTool } from "../src/lsp/tools/lspOrganizeImports.ts";
+import { lspRenameSymbolTool } from "../src/lsp/tools/lspRenameSymbol.ts";
+import { lspRunTestsTool } from "../src/lsp/tools/lspRunTests.ts";
+import { lspServerStatusTool } from "../src/lsp/tools/lspServerStatus.ts";
+import { findWorkspaceRoot } from "../src/mcp/utils/workspaceRoot.ts";
+import {
+  rustExpandMacroTool,
+  rustOpenCargoTomlTool,
+  rustParentModuleTool,
+  rustRelatedTestsTool,
+  rustStructuralReplaceTool,
+  rustViewItemTreeTool,
+} from "../src/rust/tools/index.ts";
+import {
+  findRustAnalyzer,
+  RUST_ANALYZER_INITIALIZATION_OPTIONS,
+} from "../src/rust/rustAnalyzer.ts";
+
+const RUST_PROJECT = path.join(__dirname, "../examples/rust-project");
+// Crate whose `mod errors;` fails to compile, for cargo check diagnostics
+const CARGO_CHECK_PROJECT = path.join(__dirname, "../examples/rust-cargo-check");
+// Crate with a `mod` declared module file, for moving files and removing imports
+const RUST_MODULES_PROJECT = path.join(__dirname, "../examples/rust-modules");
+const rustAnalyzer = findRustAnalyzer();
+
+describe.skipIf(!rustAnalyzer)("rust-analyzer preset", { timeout: 60000 }, () => {
+  let lspProcess: ChildProcess;
+
+  beforeAll(async () => {
+    const { root } = findWorkspaceRoot(path.join(RUST_PROJECT, "src"), "rust");
+    lspProcess = spawn(rustAnalyzer!, [], {
+      cwd: root,
+      stdio: ["pipe", "pipe", "pipe"],
+    });
+    await initializeLSPClient(
+      root,
+      lspProcess,
+      "rust",
+      RUST_ANALYZER_INITIALIZATION_OPTIONS
+    );
+  }, 60000);
+
+  afterAll(async () => {
+    await shutdownLSPClient();
+    lspProcess?.kill();
+  });
+
+  it("should resolve the cargo root of examples/rust-project", () => {
+    expect(findWorkspaceRoot(path.join(RUST_PROJECT, "src"), "rust")).toEqual({
+      root: RUST_PROJECT,
+      marker: "Cargo.toml",
+    });
+  });
+
+  it("should report idle once indexing finishes", async () => {
+    const result = await lspServerStatusTool.execute({
+      waitForIdle: true,
+      timeout: 50000,
+    });
+
+    expect(result).toContain("Status: idle");
+  });
+
+  it("should list symbols in lib.rs", async () => {
+    const result = await lspGetDocumentSymbolsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+    });
+
+    expect(result).toContain("Calculator");
+    expect(result).toContain("greet");
+  });
+
+  it("should get hover information for a Rust function", async () => {
+    const result = await lspGetHoverTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "pub fn greet",
+      target: "greet",
+    });
+
+    expect(result).toContain("greet");
+  });
+
+  it("should aggregate diagnostics across src including errors.rs", async () => {
+    const result = await lspGetWorkspaceDiagnosticsTool.execute({
+      root: RUST_PROJECT,
+      pattern: "src/**/*.rs",
+      maxFiles: 200,
+      timeout: 20000,
+    });
+
+    expect(result).toContain("Checked 3 files");
+    expect(result).toMatch(/src\/errors\.rs: \d+ errors?/);
+  });
+
+  it("should merge cargo check diagnostics for a crate module", async () => {
+    const result = await lspGetDiagnosticsTool.execute({
+      root: CARGO_CHECK_PROJECT,
+      filePath: "src/errors.rs",
+      cargoCheck: "check",
+    });
+
+    expect(result).toMatch(/Found [1-9]\d* errors?/);
+    expect(result).toContain("ERROR: mismatched types [E0308] (rustc)\n  at src/errors.rs:3:");
+  });
+
+  it("should preview a cross-file rename without touching disk", async () => {
+    const libPath = path.join(RUST_PROJECT, "src/lib.rs");
+    const before = readFileSync(libPath, "utf-8");
+
+    const result = await lspRenameSymbolTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "pub struct Calculator",
+      target: "Calculator",
+      newName: "Calc",
+      dryRun: true,
+    });
+
+    expect(result).toContain("+pub struct Calc {");
+    expect(result).toContain("+++ b/src/main.rs");
+    expect(result).toContain("+use rust_project::{Calc, greet};");
+    expect(readFileSync(libPath, "utf-8")).toBe(before);
+  });
+
+  it("should list and run a test through rust-analyzer runnables", async () => {
+    const listed = await lspRunTestsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "fn test_calculator",
+      waitForIdle: true,
+    });
+    expect(listed).toContain("test tests::test_calculator");
+
+    const result = await lspRunTestsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "fn test_calculator",
+      label: "test_calculator",
+    });
+    expect(result).toContain("Result: PASSED - 1 passed, 0 failed");
+    expect(result).toContain("PASS tests::test_calculator");
+  });
+
+  it("should expand a println! macro call", async () => {
+    const result = await rustExpandMacroTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+      line: "Calculator result",
+      target: "println",
+    });
+
+    expect(result).toContain("Recursive expansion of println! macro");
+    expect(result).toContain("_print");
+  });
+
+  it("should show the item tree of lib.rs", async () => {
+    const result = await rustViewItemTreeTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+    });
+
+    expect(result).toContain("fn greet");
+  });
+
+  it("should go to the parent module of an inline module", async () => {
+    const result = await rustParentModuleTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "fn test_calculator",
+    });
+
+    expect(result).toContain("Found 1 parent module");
+    expect(result).toContain("src/lib.rs:36:5 - tests");
+  });
+
+  it("should find the Cargo.toml of a crate file", async () => {
+    const result = await rustOpenCargoTomlTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+    });
+
+    expect(result).toContain("Cargo.toml:1:");
+  });
+
+  it("should find the tests related to a function", async () => {
+    const result = await rustRelatedTestsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "pub fn greet",
+      target: "greet",
+    });
+
+    expect(result).toContain("test tests::test_greet");
+    expect(result).toContain("src/lib.rs:47:");
+  });
+
+  it("should show the callers of greet across files", async () => {
+    const result = await lspGetCallHierarchyTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "pub fn greet",
+      target: "greet",
+      direction: "incoming",
+      depth: 1,
+    });
+
+    expect(result).toContain("Call hierarchy for greet [Function]");
+    expect(result).toContain("main [Function]");
+    expect(result).toContain("via src/main.rs:13");
+    expect(result).toContain("test_greet [Function]");
+  });
+
+  it("should find the impl blocks of a struct", async () => {
+    const result = await lspFindImplementationsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "pub struct Calculator",
+      symbolName: "Calculator",
+    });
+
+    expect(result).toContain("Found 1 implementation");
+    expect(result).toContain("src/lib.rs:6:");
+  });
+
+  it("should go to the type definition of a local variable", async () => {
+    const result = await lspGetTypeDefinitionTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+      line: "let mut calc",
+      symbolName: "calc",
+    });
+
+    expect(result).toContain("src/lib.rs:2:12 - Calculator");
+  });
+
+  it("should annotate inferred types with inlay hints", async () => {
+    const result = await lspGetInlayHintsTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+      startLine: "let mut calc",
+      endLine: "let message",
+    });
+
+    expect(result).toContain("8:     let mut calc: Calculator = Calculator::new();");
+    expect(result).toContain("let message: String = greet(");
+  });
+
+  it("should skip doc comment matches when resolving a target", async () => {
+    // "new" first appears in the doc comment "Creates a new Calculator"
+    const result = await lspGetHoverTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      target: "new",
+    });
+
+    expect(result).toContain("pub fn new() -> Self");
+  });
+
+  it("should return the enclosing method and then the impl block", async () => {
+    const method = await lspGetEnclosingRangeTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "self.value += num",
+    });
+    expect(method).toContain("src/lib.rs:12-16 (level 1 of 2)");
+    expect(method).toContain("12:     /// Adds a number to the current value");
+    expect(method).toContain("level 2: lines 6-28 - impl Calculator {");
+
+    const impl = await lspGetEnclosingRangeTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/lib.rs",
+      line: "self.value += num",
+      level: 2,
+    });
+    expect(impl).toContain("src/lib.rs:6-28 (level 2 of 2)");
+    expect(impl).toContain("28: }");
+  });
+
+  it("should preview a structural replacement without modifying files", async () => {
+    const mainPath = path.join(RUST_PROJECT, "src/main.rs");
+    const before = readFileSync(mainPath, "utf-8");
+
+    const result = await rustStructuralReplaceTool.execute({
+      root: RUST_PROJECT,
+      query: "$a.add($b) ==>> $a.plus($b)",
+      filePath: "src/main.rs",
+      dryRun: true,
+      waitForIdle: true,
+    });
+
+    expect(result).toContain("Dry run:");
+    expect(result).toContain("-    calc.add(10.0).subtract(3.0);");
+    expect(result).toContain("+    calc.plus(10.0).subtract(3.0);");
+    expect(result).toContain("calc.plus(5.0)");
+    expect(readFileSync(mainPath, "utf-8")).toBe(before);
+  });
+
+  it("should preview extracting an expression into a variable", async () => {
+    const mainPath = path.join(RUST_PROJECT, "src/main.rs");
+    const before = readFileSync(mainPath, "utf-8");
+
+    const result = await lspExtractVariableTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+      line: "Calculator result",
+      expression: "calc.get_value()",
+      dryRun: true,
+    });
+
+    expect(result).toContain("Dry run: code action Extract into variable");
+    expect(result).toMatch(/\+ +let \w+ = calc\.get_value\(\);/);
+    expect(readFileSync(mainPath, "utf-8")).toBe(before);
+  });
+
+  it("should preview inlining a local variable into its use", async () => {
+    const mainPath = path.join(RUST_PROJECT, "src/main.rs");
+    const before = readFileSync(mainPath, "utf-8");
+
+    const result = await lspInlineSymbolTool.execute({
+      root: RUST_PROJECT,
+      filePath: "src/main.rs",
+      line: "let message",
+      target: "message",
+      dryRun: true,
+    });
+
+    expect(result).toContain("Dry run: code action Inline variable");
+    expect(result).toContain('+    println!("{}", greet("Rust MCP"));');
+    expect(readFileSync(mainPath, "utf-8")).toBe(before);
+  });
+});
+
+describe.skipIf(!rustAnalyzer)("rust-analyzer file operations", { timeout: 60000 }, () => {
+  let lspProcess: ChildProcess;
+
+  beforeAll(async () => {
+    lspProcess = spawn(rustAnalyzer!, [], {
+      cwd: RUST_MODULES_PROJECT,
+      stdio: ["pipe", "pipe", "pipe"],
+    });
+    await initializeLSPClient(
+      RUST_MODULES_PROJECT,
+      lspProcess,
+      "rust",
+      RUST_ANALYZER_INITIALIZATION_OPTIONS
+    );
+  }, 60000);
+
+  afterAll(async () => {
+    await shutdownLSPClient();
+    lspProcess?.kill();
+  });
+
+  it("should preview moving a module file and its mod declaration", async () => {
+    const libPath = path.join(RUST_MODULES_PROJECT, "src/lib.rs");
+    const before = readFileSync(libPath, "utf-8");
+
+    const result = await lspMoveFileTool.execute({
+      root: RUST_MODULES_PROJECT,
+      oldPath: "src/geometry.rs",
+      newPath: "src/shapes.rs",
+      overwrite: false,
+      dryRun: true,
+      waitForIdle: true,
+    });
+
+    expect(result).toContain(
+      "Dry run: moving src/geometry.rs to src/shapes.rs would update 1 other file(s)"
+    );
+    expect(result).toContain("rename: src/geometry.rs -> src/shapes.rs");
+    expect(result).toContain("-pub mod geometry;");
+    expect(result).toContain("+pub mod shapes;");
+    expect(existsSync(path.join(RUST_MODULES_PROJECT, "src/geometry.rs"))).toBe(true);
+    expect(existsSync(path.join(RUST_MODULES_PROJECT, "src/shapes.rs"))).toBe(false);
+    expect(readFileSync(libPath, "utf-8")).toBe(before);
+  });
+
+  it("should remove all unused imports with the quick fix fallback", async () => {
+    const geometryPath = path.join(RUST_MODULES_PROJECT, "src/geometry.rs");
+    const before = readFileSync(geometryPath, "utf-8");
+
+    const result = await lspOrganizeImportsTool.execute({
+      root: RUST_MODULES_PROJECT,
+      filePath: "src/geometry.rs",
+      dryRun: true,
+      waitForIdle: true,
+    });
+
+    // rust-analyzer has no source.organizeImports action
+    expect(result).toContain("Dry run: would organize imports in 1 of 1 file(s)");
+    expect(result).toMatch(/src\/geometry\.rs \(Remove all (the )?unused imports\)/);
+    expect(result).toContain("  - use std::collections::HashMap;");
+    expect(result).toContain("  - use std::fmt::Display;");
+    expect(readFileSync(geometryPath, "utf-8")).toBe(before);
+  });
+});
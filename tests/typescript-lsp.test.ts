@@ -346,6 +346,129 @@ instance.myProperty = "value"; // Error: private property
       expect(text.toLowerCase()).toMatch(/action|fix|suggest/);
     }
   });
+
+  it("should apply a quick fix that adds a missing import", async () => {
+    if (!client) return;
+
+    await fs.writeFile(
+      path.join(tmpDir!, "double.ts"),
+      "export function double(n: number): number {\n  return n * 2;\n}\n"
+    );
+    await fs.writeFile(path.join(tmpDir!, "usedouble.ts"), "export const result = double(2);\n");
+
+    const result = await client.callTool({
+      name: "lsmcp_apply_code_action",
+      arguments: {
+        root: tmpDir,
+        filePath: "usedouble.ts",
+        startLine: 1,
+        includeKinds: ["quickfix"],
+        // Matches the single-import fix, not "Add all missing imports"
+        title: 'from "./double"',
+      },
+    });
+
+    const typedResult = result as CallToolResult;
+    const text = typedResult.content[0]?.text ?? "";
+    expect(text).toContain("Applied code action");
+    expect(text).toContain("+++ b/usedouble.ts");
+    const content = await fs.readFile(path.join(tmpDir!, "usedouble.ts"), "utf-8");
+    expect(content).toMatch(/import \{ double \} from "\.\/double";/);
+    expect(content).toContain("export const result = double(2);");
+  });
+
+  it("should extract an expression into a constant", async () => {
+    if (!client) return;
+
+    await fs.writeFile(
+      path.join(tmpDir!, "extract.ts"),
+      "export function area(width: number, height: number): number {\n  return width * height / 2;\n}\n"
+    );
+
+    const result = await client.callTool({
+      name: "lsmcp_extract_variable",
+      arguments: {
+        root: tmpDir,
+        filePath: "extract.ts",
+        line: "return width",
+        expression: "width * height",
+      },
+    });
+
+    const typedResult = result as CallToolResult;
+    const text = typedResult.content[0]?.text ?? "";
+    expect(text).toContain("Applied code action: Extract to constant");
+    const content = await fs.readFile(path.join(tmpDir!, "extract.ts"), "utf-8");
+    expect(content).toMatch(/const \w+ = width \* height;/);
+  });
+
+  it("should remove unused imports when organizing imports", async () => {
+    if (!client) return;
+
+    await fs.writeFile(
+      path.join(tmpDir!, "shapes.ts"),
+      "export const square = (n: number) => n * n;\nexport const cube = (n: number) => n * n * n;\n"
+    );
+    await fs.writeFile(
+      path.join(tmpDir!, "useshapes.ts"),
+      'import { cube, square } from "./shapes";\n\nconsole.log(square(2));\n'
+    );
+
+    const result = await client.callTool({
+      name: "lsmcp_organize_imports",
+      arguments: {
+        root: tmpDir,
+        filePath: "useshapes.ts",
+      },
+    });
+
+    const typedResult = result as CallToolResult;
+    const text = typedResult.content[0]?.text ?? "";
+    expect(text).toContain("Organized imports in 1 of 1 file(s)");
+    expect(text).toContain('  - import { cube, square } from "./shapes";');
+    expect(text).toContain('  + import { square } from "./shapes";');
+    const content = await fs.readFile(path.join(tmpDir!, "useshapes.ts"), "utf-8");
+    expect(content).not.toContain("cube");
+  });
+
+  it("should show the subtypes of an interface", async () => {
+    if (!client) return;
+
+    await fs.writeFile(
+      path.join(tmpDir!, "hierarchy.ts"),
+      [
+        "export interface Shape {",
+        "  area(): number;",
+        "}",
+        "",
+        "export class Square implements Shape {",
+        "  constructor(private size: number) {}",
+        "  area(): number {",
+        "    return this.size * this.size;",
+        "  }",
+        "}",
+        "",
+      ].join("\n")
+    );
+
+    const result = await client.callTool({
+      name: "lsmcp_get_type_hierarchy",
+      arguments: {
+        root: tmpDir,
+        filePath: "hierarchy.ts",
+        line: "export interface Shape",
+        symbolName: "Shape",
+        direction: "subtypes",
+      },
+    });
+
+    const typedResult = result as CallToolResult;
+    const text = typedResult.content[0]?.text ?? "";
+    expect(text).toContain("Type hierarchy for Shape [Interface]");
+    expect(text).toContain("Subtypes:\n  Square [Class]");
+    expect(text).toContain("hierarchy.ts:5");
+    expect(text).not.toContain("Supertypes:");
+  });
 });
 
 describe("TypeScript MCP with custom LSP via lsmcp", { timeout: 30000 }, () => {